@@ -0,0 +1,71 @@
+//! finds which version introduced each value of the last version
+//!
+//! the values of the first version all come from version 0. each version
+//! after that keeps the origin of the values that are unchanged from the
+//! version before it and marks inserted values as coming from itself.
+
+use crate::position::{PositionMap, Snap};
+use crate::shortest_edit;
+
+/// returns the index of the version that introduced each value of the last
+/// version
+pub fn annotate<T, V>(versions: &[V]) -> Vec<usize>
+where
+    T: PartialEq,
+    V: AsRef<[T]>
+{
+    let Some(first) = versions.first() else {
+        return Vec::new();
+    };
+
+    let mut origins = vec![0; first.as_ref().len()];
+
+    for (version, pair) in versions.windows(2).enumerate() {
+        let prev = pair[0].as_ref();
+        let next = pair[1].as_ref();
+        let map = PositionMap::new(prev, next, shortest_edit::linear::edits(prev, next));
+
+        origins = (0..next.len())
+            .map(|index| match map.to_a(index, Snap::None) {
+                Some(found) => origins[found],
+                None => version + 1,
+            })
+            .collect();
+    }
+
+    origins
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// splits each version into its lines
+    fn lines<'a>(versions: &[&'a str]) -> Vec<Vec<&'a str>> {
+        versions.iter().map(|version| version.lines().collect()).collect()
+    }
+
+    #[test]
+    fn single_version_is_all_from_it() {
+        assert_eq!(annotate(&lines(&["a\nb\nc"])), [0, 0, 0]);
+    }
+
+    #[test]
+    fn inserted_line_keeps_its_version() {
+        let versions = lines(&["a\nc", "a\nb\nc", "a\nb\nc\nd", "x\na\nb\nc\nd"]);
+
+        assert_eq!(annotate(&versions), [3, 0, 1, 0, 2]);
+    }
+
+    #[test]
+    fn readded_line_gets_the_newer_version() {
+        let versions = lines(&["a\nb\nc", "a\nc", "a\nb\nc"]);
+
+        assert_eq!(annotate(&versions), [0, 2, 0]);
+    }
+
+    #[test]
+    fn no_versions() {
+        assert_eq!(annotate::<&str, Vec<&str>>(&[]), Vec::<usize>::new());
+    }
+}
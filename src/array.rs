@@ -26,9 +26,9 @@ impl<'a> std::fmt::Display for PrintCharSlice<'a> {
 /// gets the specified index from the slice
 ///
 /// if the index is negative then it will start from the back of the slice
-pub fn gi<'a, T>(slice: &'a [T], index: isize) -> &'a T {
+pub fn gi<T>(slice: &[T], index: isize) -> &T {
     if index < 0 {
-        let abs = index.abs() as usize;
+        let abs = index.unsigned_abs();
 
         if abs >= slice.len() {
             panic!("index is out of range: {}", index);
@@ -49,7 +49,7 @@ pub fn gi<'a, T>(slice: &'a [T], index: isize) -> &'a T {
 /// if the index is negative then it will start from the back of the slice
 pub fn si<T>(slice: &mut [T], index: isize, value: T) {
     if index < 0 {
-        let abs = index.abs() as usize;
+        let abs = index.unsigned_abs();
 
         if abs >= slice.len() {
             panic!("index is out of range: {}", index);
@@ -65,12 +65,12 @@ pub fn si<T>(slice: &mut [T], index: isize, value: T) {
     }
 }
 
-/// retrieves a range slice using signed 32 bit integers
+/// retrieves a range slice using signed integers
 ///
 /// the start and end must be greater than 0 otherwise this will panic
 #[inline]
 #[allow(dead_code)]
-pub fn gs<'a, T>(slice: &'a [T], start: i32, end: i32) -> &'a [T] {
+pub fn gs<T>(slice: &[T], start: isize, end: isize) -> &[T] {
     &slice[(start as usize)..(end as usize)]
 }
 
@@ -86,5 +86,5 @@ where
         print!(" {v}");
     }
 
-    println!("");
+    println!();
 }
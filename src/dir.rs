@@ -0,0 +1,311 @@
+//! pairs up the files of two directories by their relative path
+//!
+//! both directories are walked together in sorted order. entries that are in
+//! only one of the directories are reported once without walking into them
+//! and entries that are files in both are paired up to be compared. symlinks
+//! are treated as files holding the path they point to so they are never
+//! followed into a directory. the pairs are then compared as a single
+//! unified diff the same as `diff -ru` or `git diff --no-index`.
+
+use std::collections::BTreeSet;
+use std::ffi::OsString;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::edit::Aligned;
+use crate::normalize::{self, Line, Options};
+use crate::shortest_edit;
+use crate::unified;
+
+/// an entry found while walking the two directories
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    /// the entry named `name` is only in the directory `parent`
+    OnlyIn { parent: PathBuf, name: OsString },
+    /// the relative path is a file in both directories
+    Files(PathBuf),
+    /// the relative path is a directory in one and a file in the other
+    Mismatch(PathBuf),
+    /// the path in one of the directories could not be read
+    Unreadable { path: PathBuf, error: String },
+}
+
+/// checks if the path is a directory without following symlinks
+pub fn is_dir(path: &Path) -> io::Result<bool> {
+    Ok(std::fs::symlink_metadata(path)?.is_dir())
+}
+
+/// reads the contents of the file or the target of the symlink
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    if std::fs::symlink_metadata(path)?.is_symlink() {
+        Ok(std::fs::read_link(path)?.into_os_string().into_encoded_bytes())
+    } else {
+        std::fs::read(path)
+    }
+}
+
+/// lists the names of the entries in the directory
+fn names(dir: &Path) -> io::Result<BTreeSet<OsString>> {
+    let mut rtn = BTreeSet::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        rtn.insert(entry?.file_name());
+    }
+
+    Ok(rtn)
+}
+
+/// walks the given relative path of both directories
+fn walk(a: &Path, b: &Path, relative: &Path, rtn: &mut Vec<Entry>) {
+    let (a_dir, b_dir) = if relative.as_os_str().is_empty() {
+        (a.to_path_buf(), b.to_path_buf())
+    } else {
+        (a.join(relative), b.join(relative))
+    };
+    let (a_names, b_names) = match (names(&a_dir), names(&b_dir)) {
+        (Ok(a_names), Ok(b_names)) => (a_names, b_names),
+        (Err(err), _) => {
+            rtn.push(Entry::Unreadable { path: a_dir, error: err.to_string() });
+            return;
+        }
+        (_, Err(err)) => {
+            rtn.push(Entry::Unreadable { path: b_dir, error: err.to_string() });
+            return;
+        }
+    };
+
+    for name in a_names.union(&b_names) {
+        if !b_names.contains(name) {
+            rtn.push(Entry::OnlyIn { parent: a_dir.clone(), name: name.clone() });
+            continue;
+        }
+
+        if !a_names.contains(name) {
+            rtn.push(Entry::OnlyIn { parent: b_dir.clone(), name: name.clone() });
+            continue;
+        }
+
+        let path = relative.join(name);
+        let a_path = a_dir.join(name);
+        let b_path = b_dir.join(name);
+
+        match (is_dir(&a_path), is_dir(&b_path)) {
+            (Ok(true), Ok(true)) => walk(a, b, &path, rtn),
+            (Ok(false), Ok(false)) => rtn.push(Entry::Files(path)),
+            (Ok(_), Ok(_)) => rtn.push(Entry::Mismatch(path)),
+            (Err(err), _) => rtn.push(Entry::Unreadable { path: a_path, error: err.to_string() }),
+            (_, Err(err)) => rtn.push(Entry::Unreadable { path: b_path, error: err.to_string() }),
+        }
+    }
+}
+
+/// pairs up the entries of the two directories
+///
+/// anything that could not be read is given as [`Entry::Unreadable`] and the
+/// rest of the directories are still walked
+pub fn pair(a: &Path, b: &Path) -> Vec<Entry> {
+    let mut rtn = Vec::new();
+
+    walk(a, b, Path::new(""), &mut rtn);
+
+    rtn
+}
+
+/// compares the files of two directories as a single unified diff
+///
+/// each file that differs starts with a `diff --git` header and entries that
+/// are only in one of the directories are listed with `Only in`. files that
+/// fail to be read are reported and skipped. returns the output along with if
+/// anything differs and if anything failed to be read
+pub fn diff(
+    from_dir: &Path,
+    to_dir: &Path,
+    options: &Options,
+    context: usize,
+    brief: bool
+) -> (String, bool, bool) {
+    let mut rtn = String::new();
+    let mut differ = false;
+    let mut trouble = false;
+
+    for entry in pair(from_dir, to_dir) {
+        let path = match entry {
+            Entry::OnlyIn { parent, name } => {
+                writeln!(rtn, "Only in {}: {}", parent.display(), name.to_string_lossy()).unwrap();
+
+                differ = true;
+                continue;
+            }
+            Entry::Mismatch(path) => {
+                let from_path = from_dir.join(&path);
+                let to_path = to_dir.join(&path);
+                let (from_kind, to_kind) = if is_dir(&from_path).unwrap_or(false) {
+                    ("directory", "regular file")
+                } else {
+                    ("regular file", "directory")
+                };
+
+                writeln!(
+                    rtn,
+                    "File {} is a {from_kind} while file {} is a {to_kind}",
+                    from_path.display(),
+                    to_path.display()
+                ).unwrap();
+
+                differ = true;
+                continue;
+            }
+            Entry::Unreadable { path, error } => {
+                eprintln!("failed to read {}: {error}", path.display());
+
+                trouble = true;
+                continue;
+            }
+            Entry::Files(path) => path,
+        };
+
+        let from_path = from_dir.join(&path);
+        let to_path = to_dir.join(&path);
+        let from_read = read(&from_path).map_err(|err| (&from_path, err));
+        let to_read = read(&to_path).map_err(|err| (&to_path, err));
+        let (from_bytes, to_bytes) = match (from_read, to_read) {
+            (Ok(from_bytes), Ok(to_bytes)) => (from_bytes, to_bytes),
+            (Err((failed, err)), _) | (_, Err((failed, err))) => {
+                eprintln!("failed to read {}: {err}", failed.display());
+
+                trouble = true;
+                continue;
+            }
+        };
+
+        if from_bytes == to_bytes {
+            continue;
+        }
+
+        let name = path.display();
+        let from_text = String::from_utf8_lossy(&from_bytes);
+        let to_text = String::from_utf8_lossy(&to_bytes);
+        let from_lines = options.lines(&from_text);
+        let to_lines = options.lines(&to_text);
+        let binary = from_bytes.contains(&0) || to_bytes.contains(&0);
+
+        if !binary && options.same(&from_lines, &to_lines) {
+            continue;
+        }
+
+        differ = true;
+
+        if brief {
+            writeln!(rtn, "Entry::Files {} and {} differ", from_path.display(), to_path.display()).unwrap();
+        } else if binary {
+            writeln!(rtn, "diff --git a/{name} b/{name}").unwrap();
+            writeln!(rtn, "Binary files a/{name} and b/{name} differ").unwrap();
+        } else {
+            let alignment: Vec<Aligned<Line>> = shortest_edit::linear::alignment(&from_lines, &to_lines).collect();
+            let suppressed = normalize::suppressed(&alignment);
+
+            writeln!(rtn, "diff --git a/{name} b/{name}").unwrap();
+            writeln!(rtn, "--- a/{name}").unwrap();
+            writeln!(rtn, "+++ b/{name}").unwrap();
+            rtn.push_str(&unified::render(
+                &alignment,
+                &suppressed,
+                context,
+                from_text.ends_with('\n'),
+                to_text.ends_with('\n')
+            ));
+        }
+    }
+
+    (rtn, differ, trouble)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// creates an empty directory for the test under the temp directory
+    fn scratch(name: &str) -> PathBuf {
+        let rtn = std::env::temp_dir().join(format!("dir-test-{}-{name}", std::process::id()));
+
+        let _ = std::fs::remove_dir_all(&rtn);
+        std::fs::create_dir_all(&rtn).unwrap();
+
+        rtn
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinks_are_not_followed() {
+        let root = scratch("symlinks");
+        let a = root.join("a");
+        let b = root.join("b");
+
+        for dir in [&a, &b] {
+            std::fs::create_dir_all(dir.join("sub")).unwrap();
+            std::os::unix::fs::symlink("..", dir.join("sub").join("loop")).unwrap();
+        }
+
+        assert_eq!(pair(&a, &b), [Entry::Files(PathBuf::from("sub").join("loop"))]);
+        assert_eq!(read(&a.join("sub").join("loop")).unwrap(), b"..");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn unreadable_directories_are_reported() {
+        let root = scratch("unreadable");
+        let a = root.join("a");
+
+        std::fs::create_dir_all(a.join("sub")).unwrap();
+        std::fs::write(a.join("file"), "").unwrap();
+
+        let entries = pair(&a, &root.join("missing"));
+
+        assert!(matches!(&entries[..], [Entry::Unreadable { path, .. }] if *path == root.join("missing")));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn diff_reports_each_kind_of_entry() {
+        let root = scratch("diff");
+        let a = root.join("a");
+        let b = root.join("b");
+
+        std::fs::create_dir_all(a.join("kind")).unwrap();
+        std::fs::create_dir_all(&b).unwrap();
+        std::fs::write(b.join("kind"), "").unwrap();
+        std::fs::write(a.join("newline"), "x\n").unwrap();
+        std::fs::write(b.join("newline"), "x").unwrap();
+        std::fs::write(a.join("only"), "").unwrap();
+        std::fs::write(a.join("same"), "y\n").unwrap();
+        std::fs::write(b.join("same"), "y\n").unwrap();
+
+        let (rendered, differ, trouble) = diff(&a, &b, &Options::default(), 3, false);
+        let expected = format!(
+            "File {} is a directory while file {} is a regular file\n\
+             diff --git a/newline b/newline\n\
+             --- a/newline\n\
+             +++ b/newline\n\
+             @@ -1 +1 @@\n\
+             -x\n\
+             +x\n\
+             \\ No newline at end of file\n\
+             Only in {}: only\n",
+            a.join("kind").display(),
+            b.join("kind").display(),
+            a.display(),
+        );
+
+        assert_eq!(rendered, expected);
+        assert!(differ && !trouble);
+
+        let (rendered, ..) = diff(&a, &b, &Options::default(), 3, true);
+
+        assert!(rendered.contains(&format!("Files {} and {} differ\n", a.join("newline").display(), b.join("newline").display())));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+}
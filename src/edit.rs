@@ -1,6 +1,6 @@
-use std::default::Default;
+use std::iter::Peekable;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Edit {
     Delete {
         pos: usize
@@ -11,7 +11,7 @@ pub enum Edit {
     },
 }
 
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct TotalEdits {
     pub inserts: usize,
     pub deletes: usize,
@@ -28,12 +28,199 @@ impl TotalEdits {
     }
 }
 
-impl Default for TotalEdits {
-    fn default() -> Self {
-        TotalEdits {
-            inserts: 0,
-            deletes: 0,
-            ops: Vec::new()
+/// a single row of an alignment
+///
+/// each side has the index and value from a and b. a delete only has a value
+/// from a, an insert only has a value from b and unchanged values have both.
+pub type Aligned<'a, T> = (Option<(usize, &'a T)>, Option<(usize, &'a T)>);
+
+/// walks a and b along side the edits needed to convert a to b
+///
+/// the edits are expected to be in the order given by
+/// [`crate::shortest_edit::operations`] or
+/// [`crate::shortest_edit::linear::modified`]. any values between the edits
+/// are treated as unchanged.
+pub struct Alignment<'a, T> {
+    a: &'a [T],
+    b: &'a [T],
+    edits: Vec<Edit>,
+    index: usize,
+    x: usize,
+    y: usize,
+}
+
+impl<'a, T> Alignment<'a, T> {
+    pub fn new<I>(a: &'a [T], b: &'a [T], edits: I) -> Self
+    where
+        I: IntoIterator<Item = Edit>
+    {
+        Alignment {
+            a,
+            b,
+            edits: edits.into_iter().collect(),
+            index: 0,
+            x: 0,
+            y: 0,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Alignment<'a, T> {
+    type Item = Aligned<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let x = self.x;
+        let y = self.y;
+
+        let next_pos = match self.edits.get(self.index) {
+            Some(Edit::Delete { pos }) | Some(Edit::Insert { pos, .. }) => *pos,
+            None => self.a.len(),
+        };
+
+        if x < next_pos && x < self.a.len() && y < self.b.len() {
+            self.x += 1;
+            self.y += 1;
+
+            return Some((Some((x, &self.a[x])), Some((y, &self.b[y]))));
+        }
+
+        match self.edits.get(self.index) {
+            Some(Edit::Delete { .. }) => {
+                self.index += 1;
+                self.x += 1;
+
+                Some((Some((x, &self.a[x])), None))
+            }
+            Some(Edit::Insert { .. }) => {
+                self.index += 1;
+                self.y += 1;
+
+                Some((None, Some((y, &self.b[y]))))
+            }
+            None => if x < self.a.len() {
+                self.x += 1;
+
+                Some((Some((x, &self.a[x])), None))
+            } else if y < self.b.len() {
+                self.y += 1;
+
+                Some((None, Some((y, &self.b[y]))))
+            } else {
+                None
+            },
+        }
+    }
+}
+
+/// a part of an alignment
+pub enum Chunk<'a, T> {
+    /// a value that is unchanged between a and b
+    Same((usize, &'a T), (usize, &'a T)),
+    /// the values deleted from a and inserted from b between two unchanged
+    /// values
+    Change(Vec<(usize, &'a T)>, Vec<(usize, &'a T)>),
+}
+
+/// groups the rows of an alignment into unchanged values and changes
+pub struct Chunks<I>
+where
+    I: Iterator
+{
+    alignment: Peekable<I>,
+}
+
+impl<I> Chunks<I>
+where
+    I: Iterator
+{
+    pub fn new<A>(alignment: A) -> Self
+    where
+        A: IntoIterator<IntoIter = I>
+    {
+        Chunks {
+            alignment: alignment.into_iter().peekable(),
+        }
+    }
+}
+
+impl<'a, T, I> Iterator for Chunks<I>
+where
+    T: 'a,
+    I: Iterator<Item = Aligned<'a, T>>
+{
+    type Item = Chunk<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut deletes = Vec::new();
+        let mut inserts = Vec::new();
+
+        while let Some(aligned) = self.alignment.peek() {
+            match *aligned {
+                (Some(a), Some(b)) => if deletes.is_empty() && inserts.is_empty() {
+                    self.alignment.next();
+
+                    return Some(Chunk::Same(a, b));
+                } else {
+                    break;
+                },
+                (Some(a), None) => deletes.push(a),
+                (None, Some(b)) => inserts.push(b),
+                (None, None) => {}
+            }
+
+            self.alignment.next();
         }
+
+        if deletes.is_empty() && inserts.is_empty() {
+            None
+        } else {
+            Some(Chunk::Change(deletes, inserts))
+        }
+    }
+}
+
+/// pairs the deletes of a change with its inserts in order
+///
+/// any deletes or inserts left over are paired with nothing
+pub fn pair<A, B>(deletes: Vec<A>, inserts: Vec<B>) -> impl Iterator<Item = (Option<A>, Option<B>)> {
+    let len = deletes.len().max(inserts.len());
+    let mut deletes = deletes.into_iter();
+    let mut inserts = inserts.into_iter();
+
+    (0..len).map(move |_| (deletes.next(), inserts.next()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_group_changes_between_unchanged_values() {
+        let a = ['a', 'b', 'c', 'd'];
+        let b = ['a', 'x', 'y', 'd'];
+        let edits = [
+            Edit::Delete { pos: 1 },
+            Edit::Delete { pos: 2 },
+            Edit::Insert { pos: 3, value: 1 },
+            Edit::Insert { pos: 3, value: 2 },
+        ];
+        let chunks: Vec<Chunk<char>> = Chunks::new(Alignment::new(&a, &b, edits)).collect();
+
+        assert_eq!(chunks.len(), 3);
+        assert!(matches!(chunks[0], Chunk::Same((0, 'a'), (0, 'a'))));
+        assert!(matches!(&chunks[1], Chunk::Change(deletes, inserts)
+            if *deletes == [(1, &'b'), (2, &'c')] && *inserts == [(1, &'x'), (2, &'y')]));
+        assert!(matches!(chunks[2], Chunk::Same((3, 'd'), (3, 'd'))));
+    }
+
+    #[test]
+    fn pair_leaves_the_extra_values_on_their_own() {
+        let paired: Vec<_> = pair(vec![1, 2, 3], vec!['a']).collect();
+
+        assert_eq!(paired, [(Some(1), Some('a')), (Some(2), None), (Some(3), None)]);
+
+        let paired: Vec<_> = pair(Vec::<u8>::new(), vec!['a', 'b']).collect();
+
+        assert_eq!(paired, [(None, Some('a')), (None, Some('b'))]);
     }
 }
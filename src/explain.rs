@@ -0,0 +1,228 @@
+//! generates a walkthrough of the algorithm for any two inputs
+//!
+//! the output is Markdown in the same layout as the writeup: the edit graph,
+//! the path taken, each depth calculated by the algorithm and the backtrack
+//! used to get the final list of edits.
+
+use std::fmt::{Display, Write};
+
+use crate::edit::{Alignment, Edit};
+use crate::graph;
+use crate::shortest_edit::{self, KChoice, Steps};
+
+/// the number of digits in the given value
+fn digits(given: usize) -> usize {
+    given.to_string().len()
+}
+
+/// writes the k steps of the given depth in the same form as the writeup
+///
+/// the widths are based on all depths so that each block lines up
+fn depth_block(rtn: &mut String, steps: &Steps, depth: usize) {
+    let data = &steps.depths[depth];
+    let signed_depth = depth as isize;
+    let k_width = digits(steps.depths.len()) + 1;
+    let all_steps = steps.depths.iter().flat_map(|data| data.ks.iter());
+    let x_width = digits(all_steps.clone().map(|step| step.x).max().unwrap_or(0));
+    let y_width = digits(all_steps.map(|step| step.y).max().unwrap_or(0));
+
+    writeln!(rtn, "depth: {depth:>k_width$} | k from {} -> {depth}", -signed_depth).unwrap();
+
+    for (step, k) in data.ks.iter().zip((-signed_depth..).step_by(2)) {
+        let choice = match step.choice {
+            KChoice::AtNegDepth => "-depth",
+            KChoice::AtDepth => " depth",
+            KChoice::Greater => "k - 1 <  k + 1",
+            KChoice::Lesser => "k - 1 >= k + 1",
+        };
+        let snake = if step.snake == 0 {
+            String::from("empty snake")
+        } else {
+            format!("{} unit snake", step.snake)
+        };
+
+        writeln!(
+            rtn,
+            "    k: {k:>k_width$} | {choice:<14} | x: {:x_width$} y: {:y_width$} | {snake:<12} | setting k[{k:>k_width$}] to x[{}]",
+            step.x,
+            step.y,
+            step.set
+        ).unwrap();
+    }
+}
+
+/// creates the walkthrough for a and b
+pub fn render<T>(a: &[T], b: &[T]) -> String
+where
+    T: PartialEq + Display
+{
+    let a_str: String = a.iter().map(|v| v.to_string()).collect();
+    let b_str: String = b.iter().map(|v| v.to_string()).collect();
+    let steps = shortest_edit::printed(a, b);
+    let result = shortest_edit::operations(a, b);
+    let edits: Vec<Edit> = result.edits.iter().map(|traced| traced.edit).collect();
+    let points = graph::path_points(Alignment::new(a, b, edits.iter().copied()));
+    let segments = shortest_edit::path(a, b);
+    let total = steps.depths.len() - 1;
+    let mut rtn = String::new();
+
+    writeln!(rtn, "# Walkthrough: `{a_str}` -> `{b_str}`\n").unwrap();
+
+    writeln!(rtn, "## Edit Graph\n").unwrap();
+    writeln!(
+        rtn,
+        "starting with `{a_str}` find the minimum amount of edits (delete or insert) \
+        necessary to create `{b_str}`. the columns are the values of `{a_str}` and the \
+        rows are the values of `{b_str}`. the goal is to start from `(0,0)` and reach \
+        `({},{})`. cells where the values are the same are marked with a diagonal.\n",
+        a.len(),
+        b.len()
+    ).unwrap();
+    writeln!(rtn, "```\n{}```\n", graph::grid(a, b, None, false)).unwrap();
+
+    let mut matches = Vec::new();
+
+    for (y, b_value) in b.iter().enumerate() {
+        for (x, a_value) in a.iter().enumerate() {
+            if a_value == b_value {
+                matches.push(format!("`({x},{y})` -> `({},{})` {a_value}", x + 1, y + 1));
+            }
+        }
+    }
+
+    if matches.is_empty() {
+        writeln!(rtn, "there are no diagonals to take.\n").unwrap();
+    } else {
+        writeln!(rtn, "the diagonals that can be taken are:\n").unwrap();
+        writeln!(rtn, "{}\n", matches.join("\\\n")).unwrap();
+    }
+
+    writeln!(rtn, "## Shortest Path\n").unwrap();
+    writeln!(
+        rtn,
+        "moving right is a delete and moving down is an insert, each costing 1 while \
+        diagonal moves cost 0. the path found has a cost of {total}.\n"
+    ).unwrap();
+
+    let mut moves = Vec::new();
+
+    for pair in points.windows(2) {
+        let (x, y) = pair[0];
+        let (u, v) = pair[1];
+
+        let (action, value, cost) = if u > x && v > y {
+            ("keep", a[x].to_string(), 0)
+        } else if u > x {
+            ("delete", a[x].to_string(), 1)
+        } else {
+            ("insert", b[y].to_string(), 1)
+        };
+
+        moves.push(format!("`({x},{y})` -> `({u},{v})` {action} {value} +{cost}"));
+    }
+
+    if !moves.is_empty() {
+        writeln!(rtn, "{}\n", moves.join("\\\n")).unwrap();
+    }
+
+    writeln!(rtn, "```\n{}```\n", graph::grid(a, b, Some(&points), false)).unwrap();
+
+    writeln!(
+        rtn,
+        "condensing the diagonal moves into the edit before them gives the following.\n"
+    ).unwrap();
+
+    let mut condensed = Vec::new();
+
+    for segment in &segments {
+        let (x, y) = segment.start;
+        let (u, v) = segment.end;
+
+        let action = match segment.edit {
+            Some(Edit::Delete { pos }) => format!("delete {}", a[pos]),
+            Some(Edit::Insert { value, .. }) => format!("insert {}", b[value]),
+            None => String::from("keep"),
+        };
+
+        condensed.push(format!("`({x},{y})` -> `({u},{v})` {action}"));
+    }
+
+    if !condensed.is_empty() {
+        writeln!(rtn, "{}\n", condensed.join("\\\n")).unwrap();
+    }
+
+    writeln!(rtn, "```\n{}```\n", graph::condensed(&segments)).unwrap();
+
+    writeln!(rtn, "## Myers Algorithm\n").unwrap();
+    writeln!(
+        rtn,
+        "each depth is the number of edits made and `k = x - y`. for every depth `k` \
+        goes from `-depth` to `depth` by steps of 2 and the furthest reaching `x` is \
+        found by either moving down from `k + 1` (insert) or right from `k - 1` \
+        (delete) of the previous depth and then following the snake.\n"
+    ).unwrap();
+
+    for depth in 0..steps.depths.len() {
+        writeln!(rtn, "```").unwrap();
+        depth_block(&mut rtn, &steps, depth);
+        writeln!(rtn).unwrap();
+        write!(rtn, "{}", graph::kgraph::render_to(&steps, depth)).unwrap();
+        writeln!(rtn, "```\n").unwrap();
+    }
+
+    writeln!(rtn, "## Backtrack\n").unwrap();
+    writeln!(
+        rtn,
+        "starting from `({},{})` at depth {total} each depth is walked back to the `k` \
+        of the previous depth that it came from giving the edit made at that depth.\n",
+        a.len(),
+        b.len()
+    ).unwrap();
+
+    let mid = (a.len() + b.len()) as isize;
+    let mut backtrack = Vec::new();
+
+    for (index, traced) in result.edits.iter().enumerate().rev() {
+        let depth = index + 1;
+        let k = traced.k as isize - mid;
+        let prev_k = if index == 0 {
+            0
+        } else {
+            result.edits[index - 1].k as isize - mid
+        };
+
+        let action = match traced.edit {
+            Edit::Delete { pos } => format!("delete {} at `{pos}`", a[pos]),
+            Edit::Insert { pos, value } => format!("insert {} at `{pos}`", b[value]),
+        };
+
+        backtrack.push(format!("depth: {depth} k: {k} <- k: {prev_k} | {action}"));
+    }
+
+    if backtrack.is_empty() {
+        writeln!(rtn, "there is nothing to backtrack since the inputs are the same.\n").unwrap();
+    } else {
+        writeln!(rtn, "```\n{}\n```\n", backtrack.join("\n")).unwrap();
+    }
+
+    writeln!(rtn, "## Edits\n").unwrap();
+    writeln!(
+        rtn,
+        "the final list of edits to convert `{a_str}` to `{b_str}` with {} inserts and {} deletes.\n",
+        result.inserts,
+        result.deletes
+    ).unwrap();
+
+    if edits.is_empty() {
+        writeln!(rtn, "there are no edits to make.").unwrap();
+    }
+
+    for edit in &edits {
+        match edit {
+            Edit::Delete { pos } => writeln!(rtn, "- delete `{}` at `{pos}`", a[*pos]).unwrap(),
+            Edit::Insert { pos, value } => writeln!(rtn, "- insert `{}` at `{pos}`", b[*value]).unwrap(),
+        }
+    }
+
+    rtn
+}
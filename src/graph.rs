@@ -0,0 +1,308 @@
+//! renders the edit graph used in the writeup
+//!
+//! the columns are the values of `a` and the rows are the values of `b`. every
+//! point `(x,y)` of the graph is drawn as a `+` with matching values being
+//! marked with a `\` in the cell between the points.
+
+use std::fmt::Display;
+
+use crate::edit::{Aligned, Edit};
+use crate::shortest_edit::{KChoice, PathSegment, Steps};
+
+pub mod dot;
+pub mod kgraph;
+pub mod svg;
+
+/// the number of characters between two points of the graph horizontally
+const CELL_WIDTH: usize = 4;
+
+/// the number of lines between two points of the graph vertically
+const CELL_HEIGHT: usize = 2;
+
+/// the column that the first point of the graph is drawn at
+const X_OFFSET: usize = 3;
+
+/// the line that the first point of the graph is drawn at
+const Y_OFFSET: usize = 1;
+
+/// a simple 2D array of characters that can be drawn on
+struct Canvas {
+    lines: Vec<Vec<char>>,
+}
+
+impl Canvas {
+    fn new(a_len: usize, b_len: usize) -> Self {
+        let width = X_OFFSET + a_len * CELL_WIDTH + 1;
+        let height = Y_OFFSET + b_len * CELL_HEIGHT + 2;
+
+        Canvas {
+            lines: vec![vec![' '; width]; height],
+        }
+    }
+
+    /// sets the character relative to the point `(x,y)` of the graph
+    fn set(&mut self, x: usize, y: usize, col: usize, line: usize, ch: char) {
+        self.lines[Y_OFFSET + y * CELL_HEIGHT + line][X_OFFSET + x * CELL_WIDTH + col] = ch;
+    }
+
+    /// draws a move from one point of the graph to the next
+    ///
+    /// diagonal moves can cover more than one cell
+    fn draw_move(&mut self, from: (usize, usize), to: (usize, usize)) {
+        let (mut x, mut y) = from;
+
+        self.set(x, y, 0, 0, '+');
+
+        if to.0 > x && to.1 > y {
+            while x < to.0 && y < to.1 {
+                self.set(x, y, CELL_WIDTH / 2, 1, '\\');
+                x += 1;
+                y += 1;
+            }
+        } else if to.0 > x {
+            for col in 1..CELL_WIDTH {
+                self.set(x, y, col, 0, '-');
+            }
+        } else if to.1 > y {
+            self.set(x, y, 0, 1, '|');
+        }
+
+        self.set(to.0, to.1, 0, 0, '+');
+    }
+
+    /// writes the string starting at the given line and column
+    fn write(&mut self, line: usize, col: usize, given: &str) {
+        for (index, ch) in given.chars().enumerate() {
+            if let Some(found) = self.lines[line].get_mut(col + index) {
+                *found = ch;
+            }
+        }
+    }
+}
+
+/// creates the list of points visited by the alignment of a and b
+///
+/// unchanged values are diagonal moves, deletes move right and inserts move
+/// down
+pub fn path_points<'a, T, I>(alignment: I) -> Vec<(usize, usize)>
+where
+    T: 'a,
+    I: Iterator<Item = Aligned<'a, T>>
+{
+    let mut x = 0;
+    let mut y = 0;
+    let mut rtn = vec![(x, y)];
+
+    for aligned in alignment {
+        if aligned.0.is_some() {
+            x += 1;
+        }
+
+        if aligned.1.is_some() {
+            y += 1;
+        }
+
+        rtn.push((x, y));
+    }
+
+    rtn
+}
+
+/// finds the points where a snake ends and the next edit starts
+///
+/// this is the condensed form of the path where each edit is combined with
+/// the diagonal moves that follow it. the first and last point are always
+/// included.
+pub fn snake_points(points: &[(usize, usize)]) -> Vec<(usize, usize)> {
+    let mut rtn = Vec::new();
+
+    for (index, point) in points.iter().enumerate() {
+        let is_end = match points.get(index + 1) {
+            Some(next) => next.0 == point.0 || next.1 == point.1,
+            None => true,
+        };
+
+        if index == 0 || is_end {
+            rtn.push(*point);
+        }
+    }
+
+    rtn
+}
+
+/// writes the values of a and b along the top and left of the graph
+fn draw_labels<T>(canvas: &mut Canvas, a: &[T], b: &[T])
+where
+    T: Display
+{
+    canvas.write(0, 1, "~");
+
+    for (x, value) in a.iter().enumerate() {
+        canvas.write(0, X_OFFSET + x * CELL_WIDTH + CELL_WIDTH / 2, &value.to_string());
+    }
+
+    for (y, value) in b.iter().enumerate() {
+        canvas.write(Y_OFFSET + y * CELL_HEIGHT + 1, 1, &value.to_string());
+    }
+}
+
+/// creates the final string from the canvas with the coordinates of the points
+/// along the right and bottom
+fn finish(canvas: Canvas, a_len: usize) -> String {
+    let mut rtn = String::new();
+
+    for (index, line) in canvas.lines.iter().enumerate() {
+        let is_point = index >= Y_OFFSET && (index - Y_OFFSET).is_multiple_of(CELL_HEIGHT);
+
+        if index == canvas.lines.len() - 1 {
+            for x in 0..=a_len {
+                rtn.push_str(&format!("{x:>CELL_WIDTH$}"));
+            }
+
+            rtn.push('\n');
+        } else if is_point {
+            rtn.extend(line.iter());
+            rtn.push_str(&format!(" {}\n", (index - Y_OFFSET) / CELL_HEIGHT));
+        } else {
+            let line: String = line.iter().collect();
+
+            rtn.push_str(line.trim_end());
+            rtn.push('\n');
+        }
+    }
+
+    rtn
+}
+
+/// renders the edit graph for a and b
+///
+/// if a path is given then only the path is drawn instead of the full grid.
+/// with snakes the end points of the condensed path are drawn as `*` instead
+/// of `+`.
+pub fn grid<T>(a: &[T], b: &[T], path: Option<&[(usize, usize)]>, snakes: bool) -> String
+where
+    T: PartialEq + Display
+{
+    let mut canvas = Canvas::new(a.len(), b.len());
+
+    draw_labels(&mut canvas, a, b);
+
+    if let Some(points) = path {
+        for pair in points.windows(2) {
+            canvas.draw_move(pair[0], pair[1]);
+        }
+
+        if snakes {
+            for (x, y) in snake_points(points) {
+                canvas.set(x, y, 0, 0, '*');
+            }
+        }
+    } else {
+        for y in 0..=b.len() {
+            for x in 0..=a.len() {
+                canvas.set(x, y, 0, 0, '+');
+
+                if x < a.len() {
+                    for col in 1..CELL_WIDTH {
+                        canvas.set(x, y, col, 0, '-');
+                    }
+                }
+
+                if y < b.len() {
+                    canvas.set(x, y, 0, 1, '|');
+
+                    if x < a.len() && a[x] == b[y] {
+                        canvas.set(x, y, CELL_WIDTH / 2, 1, '\\');
+                    }
+                }
+            }
+        }
+    }
+
+    finish(canvas, a.len())
+}
+
+/// renders the moves explored by [`crate::shortest_edit::printed`] up to and
+/// including the given depth
+///
+/// the furthest reaching points of the given depth are drawn as `*`. points
+/// that are outside of the graph are skipped.
+pub fn explored<T>(a: &[T], b: &[T], steps: &Steps, depth: usize) -> String
+where
+    T: PartialEq + Display
+{
+    let mid = a.len() + b.len();
+    let mut canvas = Canvas::new(a.len(), b.len());
+
+    draw_labels(&mut canvas, a, b);
+
+    for (current, data) in steps.depths.iter().enumerate().take(depth + 1) {
+        for (step, k) in data.ks.iter().zip((mid - current..).step_by(2)) {
+            let end = (step.set, mid + step.set - k);
+
+            if end.0 > a.len() || end.1 > b.len() {
+                continue;
+            }
+
+            if current > 0 {
+                let prev = match step.choice {
+                    KChoice::AtNegDepth | KChoice::Greater => (step.x, step.y - 1),
+                    KChoice::AtDepth | KChoice::Lesser => (step.x - 1, step.y),
+                };
+
+                canvas.draw_move(prev, (step.x, step.y));
+            }
+
+            canvas.draw_move((step.x, step.y), end);
+
+            if current == depth {
+                canvas.set(end.0, end.1, 0, 0, '*');
+            }
+        }
+    }
+
+    finish(canvas, a.len())
+}
+
+/// renders the condensed path in the same form as the writeup
+///
+/// deletes move to the right and inserts move down with each point labeled by
+/// its `x,y` coordinates. a snake at the start of the path is drawn as `===`.
+pub fn condensed(segments: &[PathSegment]) -> String {
+    let mut rtn = String::new();
+    let mut line = String::from(" 0,0");
+    let mut center = 2;
+
+    for segment in segments {
+        let label = format!("{},{}", segment.end.0, segment.end.1);
+
+        match segment.edit {
+            Some(Edit::Delete { .. }) | None => {
+                let connector = if segment.edit.is_none() { " === " } else { " --- " };
+
+                line.push_str(connector);
+
+                center = line.chars().count() + label.len() / 2;
+
+                line.push_str(&label);
+            }
+            Some(Edit::Insert { .. }) => {
+                rtn.push_str(&line);
+                rtn.push('\n');
+
+                for _ in 0..2 {
+                    rtn.push_str(&" ".repeat(center));
+                    rtn.push_str("|\n");
+                }
+
+                line = " ".repeat(center.saturating_sub(label.len() / 2));
+                line.push_str(&label);
+            }
+        }
+    }
+
+    rtn.push_str(&line);
+    rtn.push('\n');
+
+    rtn
+}
@@ -0,0 +1,79 @@
+//! renders the furthest reaching points as a Graphviz DOT graph
+//!
+//! every point found by [`crate::shortest_edit::printed`] is a node with an
+//! edge from the point of the previous depth that it was calculated from. the
+//! nodes and edges of the final path are highlighted.
+
+use std::fmt::Write;
+
+use crate::shortest_edit::{KChoice, Steps};
+
+/// the id of a node for the given depth and k
+fn node_id(depth: usize, k: isize) -> String {
+    format!("\"{depth},{k}\"")
+}
+
+/// renders the search tree of the given steps
+///
+/// `path` is the `k` of each depth on the final path starting with depth 0
+pub fn render(steps: &Steps, path: &[isize]) -> String {
+    let mut rtn = String::new();
+
+    writeln!(rtn, "digraph furthest_reaching {{").unwrap();
+    writeln!(rtn, "    rankdir=LR;").unwrap();
+    writeln!(rtn, "    node [shape=box fontname=monospace];").unwrap();
+    writeln!(rtn, "    edge [fontname=monospace fontsize=10];").unwrap();
+
+    for (depth, data) in steps.depths.iter().enumerate() {
+        let signed_depth = depth as isize;
+        let mut ids = Vec::with_capacity(data.ks.len());
+
+        writeln!(rtn).unwrap();
+
+        for (step, k) in data.ks.iter().zip((-signed_depth..).step_by(2)) {
+            let id = node_id(depth, k);
+            let on_path = path.get(depth) == Some(&k);
+            let style = if on_path {
+                " style=filled fillcolor=\"#ffd27f\" penwidth=2"
+            } else {
+                ""
+            };
+
+            writeln!(
+                rtn,
+                "    {id} [label=\"depth: {depth} k: {k}\\nx: {} y: {}\\n{} snake\"{style}];",
+                step.set,
+                step.y + step.snake,
+                step.snake
+            ).unwrap();
+
+            if depth > 0 {
+                let (prev_k, label) = match step.choice {
+                    KChoice::AtNegDepth => (k + 1, "-depth"),
+                    KChoice::AtDepth => (k - 1, "depth"),
+                    KChoice::Greater => (k + 1, "k - 1 < k + 1"),
+                    KChoice::Lesser => (k - 1, "k - 1 >= k + 1"),
+                };
+                let style = if on_path && path.get(depth - 1) == Some(&prev_k) {
+                    " color=\"#d9822b\" penwidth=2"
+                } else {
+                    ""
+                };
+
+                writeln!(
+                    rtn,
+                    "    {} -> {id} [label=\"{label}\"{style}];",
+                    node_id(depth - 1, prev_k)
+                ).unwrap();
+            }
+
+            ids.push(id);
+        }
+
+        writeln!(rtn, "    {{ rank=same; {}; }}", ids.join("; ")).unwrap();
+    }
+
+    writeln!(rtn, "}}").unwrap();
+
+    rtn
+}
@@ -0,0 +1,109 @@
+//! renders the depth by k chart used in the writeup
+//!
+//! each column is a depth and each row is a `k` with the furthest reaching `x`
+//! value found being shown where they meet. the `/` and `\` between the values
+//! link each `x` to the `k` of the previous depth that it was calculated from.
+
+use crate::shortest_edit::{KChoice, Steps};
+
+/// the number of characters between two depths
+const DEPTH_WIDTH: usize = 6;
+
+/// the number of columns at the start of each line used by the `k` labels
+const LABEL_WIDTH: usize = 7;
+
+/// the column that the value of the first depth ends on
+///
+/// values are right aligned to the column of their depth so the first depth
+/// takes up a full `DEPTH_WIDTH` after the labels
+const DEPTH_OFFSET: usize = LABEL_WIDTH + DEPTH_WIDTH;
+
+/// renders the chart for the given steps
+///
+/// the rows go from the largest `k` visited at the top to the smallest at the
+/// bottom.
+pub fn render(steps: &Steps) -> String {
+    render_to(steps, steps.depths.len().saturating_sub(1))
+}
+
+/// renders the chart with only the values up to and including the given depth
+///
+/// the rows and depths shown are the same as the full chart so that the charts
+/// of each depth line up with each other
+pub fn render_to(steps: &Steps, last: usize) -> String {
+    let max_depth = steps.depths.len().saturating_sub(1) as isize;
+    let mut max_k = 0;
+    let mut min_k = 0;
+
+    for (depth, data) in steps.depths.iter().enumerate() {
+        let depth = depth as isize;
+
+        if !data.ks.is_empty() {
+            min_k = min_k.min(-depth);
+            max_k = max_k.max(-depth + 2 * (data.ks.len() as isize - 1));
+        }
+    }
+
+    let width = DEPTH_OFFSET + DEPTH_WIDTH * (max_depth as usize) + 1;
+    let rows = (max_k - min_k + 1) as usize;
+    let mut lines = vec![vec![' '; width]; 2 * rows];
+
+    // the line of the value for k with the link line above it
+    let line_of = |k: isize| 2 * (max_k - k) as usize + 1;
+
+    for (depth, data) in steps.depths.iter().enumerate().take(last + 1) {
+        let col = DEPTH_OFFSET + depth * DEPTH_WIDTH;
+
+        for (step, k) in data.ks.iter().zip((-(depth as isize)..).step_by(2)) {
+            let line = line_of(k);
+            let value = step.set.to_string();
+
+            for (index, ch) in value.chars().rev().enumerate() {
+                lines[line][col - index] = ch;
+            }
+
+            if depth == 0 {
+                continue;
+            }
+
+            match step.choice {
+                KChoice::AtDepth | KChoice::Lesser => {
+                    lines[line + 1][col - DEPTH_WIDTH / 2] = '/';
+                }
+                KChoice::AtNegDepth | KChoice::Greater => {
+                    lines[line - 1][col - DEPTH_WIDTH / 2] = '\\';
+                }
+            }
+        }
+    }
+
+    let data_width = DEPTH_WIDTH * (max_depth as usize + 1) + 2;
+    let mut rtn = String::new();
+
+    rtn.push_str(&" ".repeat(LABEL_WIDTH + data_width / 2 - 3));
+    rtn.push_str("depth\n      | ");
+
+    for depth in 0..=max_depth {
+        rtn.push_str(&format!("{depth:>DEPTH_WIDTH$}"));
+    }
+
+    rtn.push_str(&format!("\n  ----+{}\n", "-".repeat(data_width)));
+
+    for (index, line) in lines.iter().enumerate() {
+        let line: String = line.iter().collect();
+
+        if index % 2 == 0 {
+            rtn.push_str("      |");
+        } else {
+            let k = max_k - (index / 2) as isize;
+            let label = if k == 0 { 'k' } else { ' ' };
+
+            rtn.push_str(&format!("{label}{k:>3}  |"));
+        }
+
+        rtn.push_str(line[LABEL_WIDTH..].trim_end());
+        rtn.push('\n');
+    }
+
+    rtn
+}
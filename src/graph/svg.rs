@@ -0,0 +1,179 @@
+//! renders the edit graph as a standalone SVG document
+//!
+//! along with the grid and diagonals every furthest reaching point visited by
+//! [`crate::shortest_edit::printed`] is drawn and colored by the depth it was
+//! found at. the final path is drawn on top of everything else.
+
+use std::fmt::{Display, Write};
+
+use crate::html::escape;
+use crate::shortest_edit::{KChoice, Steps};
+
+/// the size in pixels between two points of the graph
+const CELL_SIZE: usize = 40;
+
+/// the space in pixels around the graph for the labels
+const MARGIN: usize = 40;
+
+/// the pixel position of the point `(x,y)` of the graph
+fn pos(x: usize, y: usize) -> (usize, usize) {
+    (MARGIN + x * CELL_SIZE, MARGIN + y * CELL_SIZE)
+}
+
+/// the color used for the given depth
+///
+/// depths are spread evenly across the hues so that the first and last depth
+/// are easy to tell apart
+fn depth_color(depth: usize, total: usize) -> String {
+    let hue = depth * 300 / total.max(1);
+
+    format!("hsl({hue}, 80%, 45%)")
+}
+
+/// renders the edit graph, the explored frontier and the final path
+///
+/// points from the steps that are outside of the graph are skipped since the
+/// algorithm will look past the ends of a and b on the outer diagonals.
+pub fn render<T>(a: &[T], b: &[T], steps: &Steps, path: &[(usize, usize)]) -> String
+where
+    T: PartialEq + Display
+{
+    let width = 2 * MARGIN + a.len() * CELL_SIZE;
+    let height = 2 * MARGIN + b.len() * CELL_SIZE;
+    let total = steps.depths.len();
+    let mid = a.len() + b.len();
+    let mut rtn = String::new();
+
+    writeln!(
+        rtn,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="monospace" font-size="16">"#
+    ).unwrap();
+    writeln!(rtn, r#"<rect width="{width}" height="{height}" fill="white"/>"#).unwrap();
+
+    for (x, value) in a.iter().enumerate() {
+        let (px, _) = pos(x, 0);
+
+        writeln!(
+            rtn,
+            r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#,
+            px + CELL_SIZE / 2,
+            MARGIN / 2,
+            escape(&value.to_string())
+        ).unwrap();
+    }
+
+    for (y, value) in b.iter().enumerate() {
+        let (_, py) = pos(0, y);
+
+        writeln!(
+            rtn,
+            r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="middle">{}</text>"#,
+            MARGIN / 2,
+            py + CELL_SIZE / 2,
+            escape(&value.to_string())
+        ).unwrap();
+    }
+
+    writeln!(rtn, r##"<g stroke="#cccccc" stroke-width="1">"##).unwrap();
+
+    for x in 0..=a.len() {
+        let (px, top) = pos(x, 0);
+        let (_, bottom) = pos(x, b.len());
+
+        writeln!(rtn, r#"<line x1="{px}" y1="{top}" x2="{px}" y2="{bottom}"/>"#).unwrap();
+    }
+
+    for y in 0..=b.len() {
+        let (left, py) = pos(0, y);
+        let (right, _) = pos(a.len(), y);
+
+        writeln!(rtn, r#"<line x1="{left}" y1="{py}" x2="{right}" y2="{py}"/>"#).unwrap();
+    }
+
+    for (y, b_value) in b.iter().enumerate() {
+        for (x, a_value) in a.iter().enumerate() {
+            if a_value == b_value {
+                let (x1, y1) = pos(x, y);
+                let (x2, y2) = pos(x + 1, y + 1);
+
+                writeln!(
+                    rtn,
+                    r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke-dasharray="4 4"/>"#
+                ).unwrap();
+            }
+        }
+    }
+
+    writeln!(rtn, "</g>").unwrap();
+
+    for (depth, data) in steps.depths.iter().enumerate() {
+        let color = depth_color(depth, total);
+
+        writeln!(rtn, r#"<g stroke="{color}" fill="{color}" stroke-width="3">"#).unwrap();
+
+        for (step, k) in data.ks.iter().zip((mid - depth..).step_by(2)) {
+            let end_x = step.set;
+            let end_y = mid + step.set - k;
+
+            if end_x > a.len() || end_y > b.len() {
+                continue;
+            }
+
+            if depth > 0 {
+                let (prev_x, prev_y) = match step.choice {
+                    KChoice::AtNegDepth | KChoice::Greater => (step.x, step.y - 1),
+                    KChoice::AtDepth | KChoice::Lesser => (step.x - 1, step.y),
+                };
+                let (x1, y1) = pos(prev_x, prev_y);
+                let (x2, y2) = pos(step.x, step.y);
+
+                writeln!(rtn, r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}"/>"#).unwrap();
+            }
+
+            if step.snake > 0 {
+                let (x1, y1) = pos(step.x, step.y);
+                let (x2, y2) = pos(end_x, end_y);
+
+                writeln!(rtn, r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}"/>"#).unwrap();
+            }
+
+            let (cx, cy) = pos(end_x, end_y);
+
+            writeln!(
+                rtn,
+                r#"<circle cx="{cx}" cy="{cy}" r="5"><title>depth: {depth} k: {} x: {end_x} y: {end_y}</title></circle>"#,
+                k as isize - mid as isize
+            ).unwrap();
+        }
+
+        writeln!(rtn, "</g>").unwrap();
+    }
+
+    let points: Vec<String> = path.iter()
+        .map(|(x, y)| {
+            let (px, py) = pos(*x, *y);
+
+            format!("{px},{py}")
+        })
+        .collect();
+
+    writeln!(
+        rtn,
+        r#"<polyline points="{}" fill="none" stroke="black" stroke-width="2" stroke-dasharray="8 4"/>"#,
+        points.join(" ")
+    ).unwrap();
+
+    for depth in 0..total {
+        writeln!(
+            rtn,
+            r#"<text x="{}" y="{}" fill="{}" font-size="12">{depth}</text>"#,
+            MARGIN + depth * 16,
+            height - MARGIN / 4,
+            depth_color(depth, total)
+        ).unwrap();
+    }
+
+    writeln!(rtn, "</svg>").unwrap();
+
+    rtn
+}
@@ -0,0 +1,128 @@
+//! renders a diff of two lists of bytes as a side by side hex dump
+//!
+//! each row holds the same number of aligned slots for a and b. a slot has the
+//! byte of a on the left and the byte of b on the right, with deleted bytes
+//! marked by `-` and inserted bytes marked by `+`. the other side of a deleted
+//! or inserted byte is left blank so unchanged bytes always line up.
+
+use std::fmt::Write;
+
+use crate::edit::Aligned;
+
+/// the number of slots in a row
+const WIDTH: usize = 8;
+
+/// the index of a byte along with the byte
+type Byte = Option<(usize, u8)>;
+
+/// writes one side of a row
+///
+/// `next` is the offset used when the side has no bytes in the row
+fn write_side(rtn: &mut String, slots: &[Byte], marker: char, changed: &[bool], next: usize) {
+    let offset = slots.iter()
+        .flatten()
+        .map(|(index, _)| *index)
+        .next()
+        .unwrap_or(next);
+
+    write!(rtn, "{offset:08x} ").unwrap();
+
+    for (slot, is_changed) in slots.iter().zip(changed) {
+        match slot {
+            Some((_, byte)) => {
+                let mark = if *is_changed { marker } else { ' ' };
+
+                write!(rtn, " {mark}{byte:02x}").unwrap();
+            }
+            None => rtn.push_str("    "),
+        }
+    }
+
+    for _ in slots.len()..WIDTH {
+        rtn.push_str("    ");
+    }
+
+    rtn.push_str("  |");
+
+    for slot in slots {
+        match slot {
+            Some((_, byte)) if byte.is_ascii_graphic() || *byte == b' ' => rtn.push(*byte as char),
+            Some(_) => rtn.push('.'),
+            None => rtn.push(' '),
+        }
+    }
+
+    for _ in slots.len()..WIDTH {
+        rtn.push(' ');
+    }
+
+    rtn.push('|');
+}
+
+/// renders the alignment of the bytes of a and b
+pub fn render<'a, I>(alignment: I) -> String
+where
+    I: Iterator<Item = Aligned<'a, u8>>
+{
+    let slots: Vec<(Byte, Byte)> = alignment
+        .filter(|aligned| aligned.0.is_some() || aligned.1.is_some())
+        .map(|(a, b)| (a.map(|(x, byte)| (x, *byte)), b.map(|(y, byte)| (y, *byte))))
+        .collect();
+
+    let mut rtn = String::new();
+    let mut next_a = 0;
+    let mut next_b = 0;
+
+    for row in slots.chunks(WIDTH) {
+        let left: Vec<Byte> = row.iter().map(|(a, _)| *a).collect();
+        let right: Vec<Byte> = row.iter().map(|(_, b)| *b).collect();
+        let changed: Vec<bool> = row.iter().map(|(a, b)| a.is_none() || b.is_none()).collect();
+
+        write_side(&mut rtn, &left, '-', &changed, next_a);
+        rtn.push_str("  ");
+        write_side(&mut rtn, &right, '+', &changed, next_b);
+        rtn.push('\n');
+
+        next_a += left.iter().flatten().count();
+        next_b += right.iter().flatten().count();
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::edit::{Alignment, Edit};
+
+    #[test]
+    fn rows_keep_the_offset_of_sides_without_bytes() {
+        let a: Vec<u8> = [&b"ABCD"[..], &[0; 9]].concat();
+        let b: Vec<u8> = [&b"AB"[..], &[b'x'; 14], b"CD"].concat();
+        let mut edits: Vec<Edit> = (2..16).map(|value| Edit::Insert { pos: 2, value }).collect();
+
+        edits.extend((4..13).map(|pos| Edit::Delete { pos }));
+
+        let rendered = render(Alignment::new(&a, &b, edits));
+        let expected = [
+            "00000000   41  42                          |AB      |  00000000   41  42 +78 +78 +78 +78 +78 +78  |ABxxxxxx|",
+            "00000002                                   |        |  00000008  +78 +78 +78 +78 +78 +78 +78 +78  |xxxxxxxx|",
+            "00000002   43  44 -00 -00 -00 -00 -00 -00  |CD......|  00000010   43  44                          |CD      |",
+            "0000000a  -00 -00 -00                      |...     |  00000012                                   |        |",
+        ];
+
+        assert_eq!(rendered.lines().collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn changed_bytes_are_marked_on_both_sides() {
+        let a = b"a b";
+        let b = b"a\tb";
+        let edits = [Edit::Delete { pos: 1 }, Edit::Insert { pos: 2, value: 1 }];
+
+        assert_eq!(
+            render(Alignment::new(a, b, edits)),
+            "00000000   61 -20      62                  |a  b    |  00000000   61     +09  62                  |a .b    |\n"
+        );
+    }
+}
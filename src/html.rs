@@ -0,0 +1,369 @@
+//! renders a diff of two lists of lines as a standalone HTML page
+//!
+//! the page has a side by side and an inline view that can be switched
+//! between without any scripts. lines that were changed are paired up and
+//! diffed by grapheme cluster to highlight what changed within the line. long
+//! runs of unchanged lines are collapsed. lines that are part of a move are
+//! shown in their own color and are not paired with other lines.
+
+use std::fmt::Write;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::edit::{pair, Aligned, Chunk, Chunks, Edit};
+use crate::moved::Move;
+use crate::shortest_edit;
+
+/// the number of unchanged lines to show around a change
+const CONTEXT: usize = 3;
+
+const STYLE: &str = "\
+body { font-family: sans-serif; margin: 1em 2em; }
+.diff { font-family: monospace; white-space: pre-wrap; border: 1px solid #ddd; }
+.row { display: grid; }
+.side .row { grid-template-columns: 4em 1fr 4em 1fr; }
+.inline .row { grid-template-columns: 4em 4em 1.5em 1fr; }
+.num { color: #888; text-align: right; padding-right: 0.5em; user-select: none; }
+.line { padding-left: 0.25em; overflow-wrap: anywhere; }
+.del { background: #ffebe9; }
+.ins { background: #e6ffec; }
+.del del { background: #ffb3ad; text-decoration: none; }
+.ins ins { background: #9ce8ad; text-decoration: none; }
+.moved-del { background: #f3e8ff; }
+.moved-ins { background: #e8f0ff; }
+.ignored { color: #888; }
+.empty { background: #f6f8fa; }
+details > summary { background: #f1f8ff; color: #555; padding: 0.1em 0.5em; cursor: pointer; }
+.view { display: none; }
+#view-side:checked ~ .side, #view-inline:checked ~ .inline { display: block; }
+";
+
+/// the index of a line along with the line
+type Line<'a> = (usize, &'a str);
+
+/// a single row of the diff
+enum Row<'a> {
+    Equal(Line<'a>, Line<'a>),
+    Delete(Line<'a>),
+    Insert(Line<'a>),
+    Change(Line<'a>, Line<'a>),
+    MovedFrom(Line<'a>),
+    MovedTo(Line<'a>),
+    /// lines of a change that only adds or removes lines that are ignored
+    Ignored(Option<Line<'a>>, Option<Line<'a>>),
+}
+
+/// escapes the characters that are not allowed in HTML or SVG text
+pub fn escape(given: &str) -> String {
+    let mut rtn = String::with_capacity(given.len());
+
+    for ch in given.chars() {
+        match ch {
+            '&' => rtn.push_str("&amp;"),
+            '<' => rtn.push_str("&lt;"),
+            '>' => rtn.push_str("&gt;"),
+            '"' => rtn.push_str("&quot;"),
+            _ => rtn.push(ch),
+        }
+    }
+
+    rtn
+}
+
+/// creates the rows from the alignment of a and b
+///
+/// the deletes and inserts of each change are paired with each other in order
+/// with any left over being added on their own. moved values are never paired
+/// and come before the rest of the change. the changes in `suppressed` are
+/// paired up the same way but are shown as ignored.
+fn create_rows<'a, 'b, I>(alignment: I, moves: &[Move], suppressed: &[bool]) -> Vec<Row<'a>>
+where
+    'a: 'b,
+    I: Iterator<Item = Aligned<'b, &'a str>>
+{
+    let mut rows = Vec::new();
+    let mut index = 0;
+
+    for chunk in Chunks::new(alignment) {
+        match chunk {
+            Chunk::Same((x, a), (y, b)) => {
+                rows.push(Row::Equal((x, *a), (y, *b)));
+                index += 1;
+            }
+            Chunk::Change(deletes, inserts) if suppressed.get(index) == Some(&true) => {
+                index += deletes.len() + inserts.len();
+
+                for (a, b) in pair(deletes, inserts) {
+                    rows.push(Row::Ignored(a.map(|(x, a)| (x, *a)), b.map(|(y, b)| (y, *b))));
+                }
+            }
+            Chunk::Change(deletes, inserts) => {
+                index += deletes.len() + inserts.len();
+
+                let (moved_from, deletes): (Vec<_>, Vec<_>) = deletes.into_iter()
+                    .partition(|(x, _)| moves.iter().any(|found| found.contains_from(*x)));
+                let (moved_to, inserts): (Vec<_>, Vec<_>) = inserts.into_iter()
+                    .partition(|(y, _)| moves.iter().any(|found| found.contains_to(*y)));
+
+                rows.extend(moved_from.into_iter().map(|(x, a)| Row::MovedFrom((x, *a))));
+                rows.extend(moved_to.into_iter().map(|(y, b)| Row::MovedTo((y, *b))));
+
+                for paired in pair(deletes, inserts) {
+                    match paired {
+                        (Some((x, a)), Some((y, b))) => rows.push(Row::Change((x, *a), (y, *b))),
+                        (Some((x, a)), None) => rows.push(Row::Delete((x, *a))),
+                        (None, Some((y, b))) => rows.push(Row::Insert((y, *b))),
+                        (None, None) => {}
+                    }
+                }
+            }
+        }
+    }
+
+    rows
+}
+
+/// diffs the grapheme clusters of two lines and wraps the changed ones
+///
+/// deleted graphemes of a are wrapped in `<del>` and inserted graphemes of b
+/// are wrapped in `<ins>`
+fn highlight(a: &str, b: &str) -> (String, String) {
+    let a_graphemes: Vec<&str> = a.graphemes(true).collect();
+    let b_graphemes: Vec<&str> = b.graphemes(true).collect();
+    let edits = shortest_edit::linear::edits(&a_graphemes, &b_graphemes);
+
+    let mut deleted = vec![false; a_graphemes.len()];
+    let mut inserted = vec![false; b_graphemes.len()];
+
+    for edit in edits {
+        match edit {
+            Edit::Delete { pos } => deleted[pos] = true,
+            Edit::Insert { value, .. } => inserted[value] = true,
+        }
+    }
+
+    (wrap(&a_graphemes, &deleted, "del"), wrap(&b_graphemes, &inserted, "ins"))
+}
+
+/// wraps each run of marked graphemes in the given tag
+fn wrap(graphemes: &[&str], marked: &[bool], tag: &str) -> String {
+    let mut rtn = String::new();
+    let mut index = 0;
+
+    while index < graphemes.len() {
+        let start = index;
+
+        while index < graphemes.len() && marked[index] == marked[start] {
+            index += 1;
+        }
+
+        let text = graphemes[start..index].concat();
+
+        if marked[start] {
+            write!(rtn, "<{tag}>{}</{tag}>", escape(&text)).unwrap();
+        } else {
+            rtn.push_str(&escape(&text));
+        }
+    }
+
+    rtn
+}
+
+/// writes the rows of equal lines collapsing the middle of long runs
+fn write_equal<F>(rtn: &mut String, rows: &[(Line, Line)], at_start: bool, at_end: bool, mut row: F)
+where
+    F: FnMut(&mut String, Line, Line)
+{
+    let head = if at_start { 0 } else { CONTEXT };
+    let tail = if at_end { 0 } else { CONTEXT };
+
+    if rows.len() <= head + tail + 1 {
+        for (a, b) in rows {
+            row(rtn, *a, *b);
+        }
+
+        return;
+    }
+
+    for (a, b) in &rows[..head] {
+        row(rtn, *a, *b);
+    }
+
+    let hidden = &rows[head..rows.len() - tail];
+
+    write!(rtn, "<details><summary>{} unchanged lines</summary>", hidden.len()).unwrap();
+
+    for (a, b) in hidden {
+        row(rtn, *a, *b);
+    }
+
+    rtn.push_str("</details>");
+
+    for (a, b) in &rows[rows.len() - tail..] {
+        row(rtn, *a, *b);
+    }
+}
+
+/// writes both views of the rows
+fn write_views(rtn: &mut String, rows: &[Row]) {
+    let mut side = String::new();
+    let mut inline = String::new();
+    let mut index = 0;
+
+    while index < rows.len() {
+        if let Row::Equal(..) = rows[index] {
+            let start = index;
+            let mut equal = Vec::new();
+
+            while let Some(Row::Equal(a, b)) = rows.get(index) {
+                equal.push((*a, *b));
+                index += 1;
+            }
+
+            let at_start = start == 0;
+            let at_end = index == rows.len();
+
+            write_equal(&mut side, &equal, at_start, at_end, |rtn, (x, a), (y, b)| {
+                write!(
+                    rtn,
+                    "<div class=\"row\"><span class=\"num\">{}</span><span class=\"line\">{}</span><span class=\"num\">{}</span><span class=\"line\">{}</span></div>",
+                    x + 1,
+                    escape(a),
+                    y + 1,
+                    escape(b)
+                ).unwrap();
+            });
+            write_equal(&mut inline, &equal, at_start, at_end, |rtn, (x, a), (y, _)| {
+                write!(
+                    rtn,
+                    "<div class=\"row\"><span class=\"num\">{}</span><span class=\"num\">{}</span><span></span><span class=\"line\">{}</span></div>",
+                    x + 1,
+                    y + 1,
+                    escape(a)
+                ).unwrap();
+            });
+
+            continue;
+        }
+
+        let (left, right, del_class, ins_class, del_mark, ins_mark) = match rows[index] {
+            Row::Change((x, a), (y, b)) => {
+                let (left, right) = highlight(a, b);
+
+                (Some((x, left)), Some((y, right)), "del", "ins", "-", "+")
+            }
+            Row::Delete((x, a)) => (Some((x, escape(a))), None, "del", "ins", "-", "+"),
+            Row::Insert((y, b)) => (None, Some((y, escape(b))), "del", "ins", "-", "+"),
+            Row::MovedFrom((x, a)) => (Some((x, escape(a))), None, "moved-del", "moved-ins", "&lt;", "&gt;"),
+            Row::MovedTo((y, b)) => (None, Some((y, escape(b))), "moved-del", "moved-ins", "&lt;", "&gt;"),
+            Row::Ignored(a, b) => (
+                a.map(|(x, a)| (x, escape(a))),
+                b.map(|(y, b)| (y, escape(b))),
+                "ignored",
+                "ignored",
+                "-",
+                "+"
+            ),
+            Row::Equal(..) => unreachable!(),
+        };
+
+        side.push_str("<div class=\"row\">");
+
+        match &left {
+            Some((x, line)) => write!(
+                side,
+                "<span class=\"num {del_class}\">{}</span><span class=\"line {del_class}\">{line}</span>",
+                x + 1
+            ).unwrap(),
+            None => side.push_str("<span class=\"num empty\"></span><span class=\"line empty\"></span>"),
+        }
+
+        match &right {
+            Some((y, line)) => write!(
+                side,
+                "<span class=\"num {ins_class}\">{}</span><span class=\"line {ins_class}\">{line}</span>",
+                y + 1
+            ).unwrap(),
+            None => side.push_str("<span class=\"num empty\"></span><span class=\"line empty\"></span>"),
+        }
+
+        side.push_str("</div>");
+
+        if let Some((x, line)) = &left {
+            write!(
+                inline,
+                "<div class=\"row {del_class}\"><span class=\"num\">{}</span><span class=\"num\"></span><span>{del_mark}</span><span class=\"line\">{line}</span></div>",
+                x + 1
+            ).unwrap();
+        }
+
+        if let Some((y, line)) = &right {
+            write!(
+                inline,
+                "<div class=\"row {ins_class}\"><span class=\"num\"></span><span class=\"num\">{}</span><span>{ins_mark}</span><span class=\"line\">{line}</span></div>",
+                y + 1
+            ).unwrap();
+        }
+
+        index += 1;
+    }
+
+    writeln!(rtn, "<div class=\"view side diff\">{side}</div>").unwrap();
+    writeln!(rtn, "<div class=\"view inline diff\">{inline}</div>").unwrap();
+}
+
+/// renders the page for the alignment of the lines of a and b
+///
+/// the lines that are part of the given moves are shown as moved and the rows
+/// in `suppressed` are shown as ignored
+pub fn render<'a, 'b, I>(from_name: &str, to_name: &str, alignment: I, moves: &[Move], suppressed: &[bool]) -> String
+where
+    'a: 'b,
+    I: Iterator<Item = Aligned<'b, &'a str>>
+{
+    let rows = create_rows(alignment, moves, suppressed);
+    let mut inserts = 0;
+    let mut deletes = 0;
+    let mut moved = 0;
+
+    for row in &rows {
+        match row {
+            Row::Equal(..) => {}
+            Row::Delete(_) => deletes += 1,
+            Row::Insert(_) => inserts += 1,
+            Row::Change(..) => {
+                deletes += 1;
+                inserts += 1;
+            }
+            Row::MovedFrom(_) => moved += 1,
+            Row::MovedTo(_) | Row::Ignored(..) => {}
+        }
+    }
+
+    let title = format!("{} -&gt; {}", escape(from_name), escape(to_name));
+    let mut rtn = String::new();
+
+    writeln!(rtn, "<!DOCTYPE html>").unwrap();
+    writeln!(rtn, "<html>").unwrap();
+    writeln!(rtn, "<head>").unwrap();
+    writeln!(rtn, "<meta charset=\"utf-8\">").unwrap();
+    writeln!(rtn, "<title>{title}</title>").unwrap();
+    writeln!(rtn, "<style>\n{STYLE}</style>").unwrap();
+    writeln!(rtn, "</head>").unwrap();
+    writeln!(rtn, "<body>").unwrap();
+    writeln!(rtn, "<h1>{title}</h1>").unwrap();
+    if moves.is_empty() {
+        writeln!(rtn, "<p>inserts: {inserts} deletes: {deletes}</p>").unwrap();
+    } else {
+        writeln!(rtn, "<p>inserts: {inserts} deletes: {deletes} moved: {moved}</p>").unwrap();
+    }
+    writeln!(rtn, "<input type=\"radio\" name=\"view\" id=\"view-side\" checked><label for=\"view-side\">side by side</label>").unwrap();
+    writeln!(rtn, "<input type=\"radio\" name=\"view\" id=\"view-inline\"><label for=\"view-inline\">inline</label>").unwrap();
+
+    write_views(&mut rtn, &rows);
+
+    writeln!(rtn, "</body>").unwrap();
+    writeln!(rtn, "</html>").unwrap();
+
+    rtn
+}
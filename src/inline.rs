@@ -0,0 +1,142 @@
+//! renders an alignment back onto the text with the changes marked inline
+//!
+//! unchanged values are written as they are while the deleted and inserted
+//! values of each change are joined and marked in the style given, similar to
+//! git's `--word-diff`.
+
+use std::fmt::{Display, Write};
+
+use crate::edit::{Aligned, Chunk, Chunks};
+
+/// how the changes are shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Style {
+    /// wraps deleted text in [-...-] and inserted text in {+...+}
+    Plain,
+
+    /// shows deleted text in red and inserted text in green
+    Color,
+
+    /// a line based format for scripts where each piece of text is on its own
+    /// line starting with ' ', '-' or '+' and '~' marks a newline
+    Porcelain,
+}
+
+/// renders the alignment back onto the text in the given word diff style
+///
+/// unchanged text is taken from b
+pub fn render<'a, T, I>(alignment: I, style: Style) -> String
+where
+    T: Display + 'a,
+    I: Iterator<Item = Aligned<'a, T>>
+{
+    let mut rtn = String::new();
+    let mut kept = String::new();
+
+    for chunk in Chunks::new(alignment) {
+        match chunk {
+            Chunk::Same(_, (_, value)) => write!(kept, "{value}").unwrap(),
+            Chunk::Change(deletes, inserts) => {
+                if !kept.is_empty() {
+                    write(&mut rtn, ' ', &kept, style);
+                    kept.clear();
+                }
+
+                let deleted: String = deletes.iter().map(|(_, value)| value.to_string()).collect();
+                let inserted: String = inserts.iter().map(|(_, value)| value.to_string()).collect();
+
+                if !deleted.is_empty() {
+                    write(&mut rtn, '-', &deleted, style);
+                }
+
+                if !inserted.is_empty() {
+                    write(&mut rtn, '+', &inserted, style);
+                }
+            }
+        }
+    }
+
+    if !kept.is_empty() {
+        write(&mut rtn, ' ', &kept, style);
+    }
+
+    if style == Style::Porcelain && !rtn.is_empty() && !rtn.ends_with("~\n") {
+        rtn.push_str("~\n");
+    }
+
+    rtn
+}
+
+/// writes text that is unchanged (` `), deleted (`-`) or inserted (`+`)
+///
+/// porcelain puts each piece of text on its own line starting with the
+/// marker and uses a line with only `~` for the newlines of the text
+fn write(rtn: &mut String, marker: char, text: &str, style: Style) {
+    match (style, marker) {
+        (Style::Porcelain, _) => {
+            for (index, piece) in text.split('\n').enumerate() {
+                if index > 0 {
+                    rtn.push_str("~\n");
+                }
+
+                if !piece.is_empty() {
+                    writeln!(rtn, "{marker}{piece}").unwrap();
+                }
+            }
+        }
+        (_, ' ') => rtn.push_str(text),
+        (Style::Plain, '-') => write!(rtn, "[-{text}-]").unwrap(),
+        (Style::Plain, _) => write!(rtn, "{{+{text}+}}").unwrap(),
+        (Style::Color, _) => {
+            let color = if marker == '-' { "31" } else { "32" };
+
+            for (index, piece) in text.split('\n').enumerate() {
+                if index > 0 {
+                    rtn.push('\n');
+                }
+
+                if !piece.is_empty() {
+                    write!(rtn, "\x1b[{color}m{piece}\x1b[m").unwrap();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortest_edit;
+    use crate::token;
+
+    /// renders the word diff of the two texts in the given style
+    fn rendered(a: &str, b: &str, style: Style) -> String {
+        let a_words = token::words(a);
+        let b_words = token::words(b);
+
+        render(shortest_edit::linear::alignment(&a_words, &b_words), style)
+    }
+
+    #[test]
+    fn plain_wraps_the_changes() {
+        assert_eq!(rendered("a b c\nd\n", "a x c\nd\n", Style::Plain), "a [-b-]{+x+} c\nd\n");
+        assert_eq!(rendered("a\n", "a b\n", Style::Plain), "a{+ b+}\n");
+    }
+
+    #[test]
+    fn color_marks_each_line_of_a_change() {
+        assert_eq!(
+            rendered("a b\n", "a x\ny\n", Style::Color),
+            "a \x1b[31mb\x1b[m\x1b[32mx\x1b[m\n\x1b[32my\x1b[m\n"
+        );
+    }
+
+    #[test]
+    fn porcelain_puts_each_piece_on_its_own_line() {
+        assert_eq!(
+            rendered("a b\nc\n", "a x\nc\n", Style::Porcelain),
+            " a \n-b\n+x\n~\n c\n~\n"
+        );
+        assert_eq!(rendered("a", "b", Style::Porcelain), "-a\n+b\n~\n");
+    }
+}
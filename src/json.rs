@@ -0,0 +1,307 @@
+//! structural diff of two JSON documents
+//!
+//! objects are compared by key and arrays are compared by running
+//! [`shortest_edit::linear::edits`] over their elements. elements of an
+//! array that were changed are paired up, objects with objects and arrays with
+//! arrays first, and compared recursively so that only the parts of the
+//! element that changed are reported.
+//!
+//! each change has a JSON pointer (RFC 6901) to the value that changed. the
+//! indices of arrays in the pointers take into account the changes before it
+//! so applying the changes in order gives the second document which lets them
+//! be written out as a JSON patch (RFC 6902).
+
+use std::fmt::Write;
+
+use serde_json::{json, Value};
+
+use crate::edit::{pair, Chunk, Chunks};
+use crate::shortest_edit;
+
+/// a single change between two documents
+#[derive(Debug, Clone, PartialEq)]
+pub enum Change {
+    /// the value was added at the path
+    Add { path: String, value: Value },
+    /// the value at the path was removed
+    Remove { path: String, value: Value },
+    /// the value at the path was replaced
+    Replace { path: String, from: Value, to: Value },
+}
+
+/// escapes a key to be used as a token of a JSON pointer
+fn escape(given: &str) -> String {
+    given.replace('~', "~0").replace('/', "~1")
+}
+
+/// compares the objects by key
+fn diff_objects(
+    changes: &mut Vec<Change>,
+    path: &str,
+    a: &serde_json::Map<String, Value>,
+    b: &serde_json::Map<String, Value>
+) {
+    for (key, a_value) in a {
+        let key_path = format!("{path}/{}", escape(key));
+
+        match b.get(key) {
+            Some(b_value) => diff_values(changes, &key_path, a_value, b_value),
+            None => changes.push(Change::Remove { path: key_path, value: a_value.clone() }),
+        }
+    }
+
+    for (key, b_value) in b {
+        if !a.contains_key(key) {
+            changes.push(Change::Add { path: format!("{path}/{}", escape(key)), value: b_value.clone() });
+        }
+    }
+}
+
+/// checks if both values are objects or both are arrays
+fn same_container(a: &Value, b: &Value) -> bool {
+    matches!((a, b), (Value::Object(_), Value::Object(_)) | (Value::Array(_), Value::Array(_)))
+}
+
+/// finds the deletes and inserts of a change that are the same kind of
+/// container, in order, so they can be compared recursively
+fn match_containers(deletes: &[(usize, &Value)], inserts: &[(usize, &Value)]) -> Vec<(usize, usize)> {
+    let mut rtn = Vec::new();
+    let mut next = 0;
+
+    for (delete_index, (_, a_value)) in deletes.iter().enumerate() {
+        let found = inserts[next..].iter()
+            .position(|(_, b_value)| same_container(a_value, b_value));
+
+        if let Some(offset) = found {
+            rtn.push((delete_index, next + offset));
+            next += offset + 1;
+        }
+    }
+
+    rtn
+}
+
+/// compares a deleted value with the inserted value it is paired with
+///
+/// a value without a pair is removed or added on its own
+fn diff_paired(changes: &mut Vec<Change>, path: &str, index: &mut usize, paired: (Option<&Value>, Option<&Value>)) {
+    match paired {
+        (Some(a_value), Some(b_value)) => {
+            diff_values(changes, &format!("{path}/{index}"), a_value, b_value);
+
+            *index += 1;
+        }
+        (Some(a_value), None) => {
+            changes.push(Change::Remove { path: format!("{path}/{index}"), value: a_value.clone() });
+        }
+        (None, Some(b_value)) => {
+            changes.push(Change::Add { path: format!("{path}/{index}"), value: b_value.clone() });
+
+            *index += 1;
+        }
+        (None, None) => {}
+    }
+}
+
+/// compares the arrays by element
+///
+/// the deletes and inserts of each change that are the same kind of container
+/// are paired first so that they are compared recursively. the values between
+/// them are paired with each other in order and any left over are removed or
+/// added on their own. `index` is the position in the array after the
+/// previous changes were applied.
+fn diff_arrays(changes: &mut Vec<Change>, path: &str, a: &[Value], b: &[Value]) {
+    let mut index = 0;
+
+    for chunk in Chunks::new(shortest_edit::linear::alignment(a, b)) {
+        let Chunk::Change(deletes, inserts) = chunk else {
+            index += 1;
+            continue;
+        };
+
+        let mut delete_start = 0;
+        let mut insert_start = 0;
+        let matched = match_containers(&deletes, &inserts);
+        let end = (deletes.len(), inserts.len());
+
+        for (delete_end, insert_end) in matched.into_iter().chain([end]) {
+            let between = pair(
+                deletes[delete_start..delete_end].iter().map(|(_, value)| *value).collect(),
+                inserts[insert_start..insert_end].iter().map(|(_, value)| *value).collect(),
+            );
+
+            for paired in between {
+                diff_paired(changes, path, &mut index, paired);
+            }
+
+            if let (Some((_, a_value)), Some((_, b_value))) = (deletes.get(delete_end), inserts.get(insert_end)) {
+                diff_paired(changes, path, &mut index, (Some(*a_value), Some(*b_value)));
+            }
+
+            delete_start = delete_end + 1;
+            insert_start = insert_end + 1;
+        }
+    }
+}
+
+/// compares the values at the given path
+fn diff_values(changes: &mut Vec<Change>, path: &str, a: &Value, b: &Value) {
+    match (a, b) {
+        (Value::Object(a_map), Value::Object(b_map)) => diff_objects(changes, path, a_map, b_map),
+        (Value::Array(a_list), Value::Array(b_list)) => diff_arrays(changes, path, a_list, b_list),
+        _ => if a != b {
+            changes.push(Change::Replace { path: path.to_owned(), from: a.clone(), to: b.clone() });
+        },
+    }
+}
+
+/// finds the changes needed to convert a to b
+pub fn diff(a: &Value, b: &Value) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    diff_values(&mut changes, "", a, b);
+
+    changes
+}
+
+/// lists each change with its path
+///
+/// added values are marked with `+`, removed values with `-` and replaced
+/// values with `~`
+pub fn render(changes: &[Change]) -> String {
+    let mut rtn = String::new();
+
+    for change in changes {
+        match change {
+            Change::Add { path, value } => writeln!(rtn, "+ {path}: {value}").unwrap(),
+            Change::Remove { path, value } => writeln!(rtn, "- {path}: {value}").unwrap(),
+            Change::Replace { path, from, to } => writeln!(rtn, "~ {path}: {from} -> {to}").unwrap(),
+        }
+    }
+
+    rtn
+}
+
+/// creates a JSON patch from the changes
+pub fn patch(changes: &[Change]) -> Value {
+    let operations = changes.iter()
+        .map(|change| match change {
+            Change::Add { path, value } => json!({ "op": "add", "path": path, "value": value }),
+            Change::Remove { path, .. } => json!({ "op": "remove", "path": path }),
+            Change::Replace { path, to, .. } => json!({ "op": "replace", "path": path, "value": to }),
+        })
+        .collect();
+
+    Value::Array(operations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// gets the parent of the value the pointer points to along with the last
+    /// token of the pointer
+    fn parent<'a>(root: &'a mut Value, pointer: &str) -> (&'a mut Value, String) {
+        let (parent_pointer, last) = pointer.rsplit_once('/').unwrap();
+        let last = last.replace("~1", "/").replace("~0", "~");
+
+        (root.pointer_mut(parent_pointer).unwrap(), last)
+    }
+
+    /// applies the JSON patch to the value
+    fn apply(mut value: Value, patch: &Value) -> Value {
+        for operation in patch.as_array().unwrap() {
+            let path = operation["path"].as_str().unwrap();
+
+            if path.is_empty() {
+                value = operation["value"].clone();
+                continue;
+            }
+
+            let (target, last) = parent(&mut value, path);
+
+            match (operation["op"].as_str().unwrap(), target) {
+                ("add", Value::Array(list)) => list.insert(last.parse().unwrap(), operation["value"].clone()),
+                ("add", Value::Object(map)) => {
+                    map.insert(last, operation["value"].clone());
+                }
+                ("remove", Value::Array(list)) => {
+                    list.remove(last.parse().unwrap());
+                }
+                ("remove", Value::Object(map)) => {
+                    map.remove(&last);
+                }
+                ("replace", Value::Array(list)) => list[last.parse::<usize>().unwrap()] = operation["value"].clone(),
+                ("replace", Value::Object(map)) => {
+                    map.insert(last, operation["value"].clone());
+                }
+                (op, target) => panic!("can not {op} {path} in {target}"),
+            }
+        }
+
+        value
+    }
+
+    /// checks that applying the patch of the changes to a gives b
+    fn check_patch(a: Value, b: Value) -> Vec<Change> {
+        let changes = diff(&a, &b);
+
+        assert_eq!(apply(a.clone(), &patch(&changes)), b, "{a} -> {b}");
+
+        changes
+    }
+
+    #[test]
+    fn patch_inserts_before() {
+        let changes = check_patch(json!([1, 2, 3]), json!([0, 1, 2, 3]));
+
+        assert_eq!(changes, [Change::Add { path: "/0".into(), value: json!(0) }]);
+    }
+
+    #[test]
+    fn patch_deletes_after_a_replace() {
+        check_patch(json!([1, 2, 3, 4]), json!([5, 4]));
+        check_patch(json!({ "a": [1, 2, 3], "b": "x" }), json!({ "a": [9], "c": "x" }));
+    }
+
+    #[test]
+    fn patch_reverses_arrays() {
+        check_patch(json!([1, 2, 3, 4, 5]), json!([5, 4, 3, 2, 1]));
+        check_patch(json!(["a", "b", "c"]), json!(["c", "b", "a"]));
+    }
+
+    #[test]
+    fn patch_recurses_into_objects_in_arrays() {
+        let changes = check_patch(
+            json!([{ "id": 1, "tags": ["x"] }, { "id": 2 }]),
+            json!([{ "id": 1, "tags": ["x", "y"] }, { "id": 3 }]),
+        );
+
+        assert_eq!(changes, [
+            Change::Add { path: "/0/tags/1".into(), value: json!("y") },
+            Change::Replace { path: "/1/id".into(), from: json!(2), to: json!(3) },
+        ]);
+    }
+
+    #[test]
+    fn same_kind_containers_are_paired_first() {
+        let changes = check_patch(json!({ "a": [1, 2, { "x": 1 }] }), json!({ "a": [0, 1, { "x": 2 }, 3] }));
+
+        assert_eq!(changes, [
+            Change::Add { path: "/a/0".into(), value: json!(0) },
+            Change::Remove { path: "/a/2".into(), value: json!(2) },
+            Change::Replace { path: "/a/2/x".into(), from: json!(1), to: json!(2) },
+            Change::Add { path: "/a/3".into(), value: json!(3) },
+        ]);
+    }
+
+    #[test]
+    fn keys_are_escaped_in_the_pointer() {
+        let changes = check_patch(json!({ "a/b": 1, "c~d": 2 }), json!({ "a/b": 2 }));
+
+        assert_eq!(changes, [
+            Change::Replace { path: "/a~1b".into(), from: json!(1), to: json!(2) },
+            Change::Remove { path: "/c~0d".into(), value: json!(2) },
+        ]);
+    }
+}
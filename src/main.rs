@@ -1,11 +1,33 @@
-use clap::{Parser, Subcommand};
+use std::fmt::Write as FmtWrite;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::ExitCode;
 
+use clap::{Parser, Subcommand, ValueEnum};
+use unicode_segmentation::UnicodeSegmentation;
+
+mod annotate;
 mod array;
+mod dir;
 mod edit;
+mod explain;
+mod graph;
+mod hex;
+mod html;
+mod inline;
+mod json;
+mod moved;
+mod normalize;
+mod position;
 mod shortest_edit;
+mod table;
+mod token;
+mod unified;
 
 #[derive(Debug, Parser)]
 #[command(max_term_width(80))]
+#[command(after_help("the diff, json and csv commands exit with 0 if the inputs are the same, 1 if \
+they differ and 2 if there was an error"))]
 struct AppArgs {
     #[command(subcommand)]
     op: Operation,
@@ -17,6 +39,20 @@ struct AppArgs {
     /// the string you want to convert to
     #[arg(short, long, default_value("cbabac"))]
     to: String,
+
+    /// how to split from and to into characters
+    #[arg(long, value_enum, default_value_t = CharMode::Grapheme)]
+    units: CharMode,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum CharMode {
+    /// splits on user perceived characters (extended grapheme clusters) so
+    /// emoji and combining accents are kept whole
+    Grapheme,
+
+    /// splits on each unicode scalar value
+    Char,
 }
 
 #[derive(Debug, Subcommand)]
@@ -37,16 +73,284 @@ enum Operation {
 
     /// lists the operations needed to convert string b to a
     Operations,
+
+    /// lists the operations needed to convert string b to a using the O(NM)
+    /// dynamic programming table instead of Myers algorithm
+    Dp,
+
+    /// draws the edit graph of the two strings
+    Graph {
+        /// draws the path chosen by operations instead of the full grid
+        #[arg(long)]
+        path: bool,
+
+        /// marks the end points of the condensed snakes with a `*`
+        #[arg(long, requires("path"))]
+        snakes: bool,
+
+        /// the format to draw the graph in
+        #[arg(long, value_enum, default_value_t = GraphFormat::Ascii)]
+        format: GraphFormat,
+
+        /// the file to write the graph to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// draws the x value found for each k at each depth along with the k of
+    /// the previous depth it came from
+    Kgraph,
+
+    /// walks through each depth of printed one at a time using commands read
+    /// from stdin
+    Step,
+
+    /// prints out each level of recursion the linear space algorithm takes
+    /// along with the forward and reverse passes and the middle snake found
+    LinearPrinted,
+
+    /// lists each edit combined with the snake that follows it along with the
+    /// condensed trace of the path
+    Path,
+
+    /// maps each position of from to its position in to, or each position of
+    /// to back to from with --reverse
+    Position {
+        /// maps the positions of to back to from
+        #[arg(long)]
+        reverse: bool,
+
+        /// how to map a position that was deleted or inserted
+        #[arg(long, value_enum, default_value_t = position::Snap::None)]
+        snap: position::Snap,
+    },
+
+    /// generates a Markdown walkthrough of the algorithm similar to the
+    /// writeup
+    Explain {
+        /// the file to write the walkthrough to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// shows which file introduced each line of the last file with the files
+    /// given in order from oldest to newest
+    Blame {
+        /// the versions of the file from oldest to newest
+        #[arg(required(true))]
+        files: Vec<PathBuf>,
+    },
+
+    /// compares the lines of two files or the lines of from and to if no files
+    /// are given
+    Diff {
+        /// the file you want to convert from
+        #[arg(requires("to_file"))]
+        from_file: Option<PathBuf>,
+
+        /// the file you want to convert to
+        to_file: Option<PathBuf>,
+
+        /// what to split the input into before comparing
+        #[arg(long, value_enum, default_value_t = DiffMode::Line)]
+        mode: DiffMode,
+
+        /// the format to output the differences in. html is only available
+        /// for the line mode
+        #[arg(long, value_enum, default_value_t = DiffFormat::Text)]
+        format: DiffFormat,
+
+        /// how to show the changes within the text for the modes other than
+        /// line
+        #[arg(long, value_enum, default_value_t = inline::Style::Plain)]
+        word_diff: inline::Style,
+
+        /// the pattern that matches a word for the word mode instead of
+        /// splitting on whitespace
+        #[arg(long)]
+        word_regex: Option<String>,
+
+        /// compares the bytes of the inputs showing them as a side by side
+        /// hex dump. without this inputs containing a NUL byte are treated as
+        /// binary and only reported as being different
+        #[arg(long)]
+        hex: bool,
+
+        /// ignores all whitespace when comparing lines
+        #[arg(short = 'w', long)]
+        ignore_all_space: bool,
+
+        /// ignores changes in the amount of whitespace when comparing lines
+        #[arg(short = 'b', long)]
+        ignore_space_change: bool,
+
+        /// ignores changes that only add or remove blank lines
+        #[arg(short = 'B', long)]
+        ignore_blank_lines: bool,
+
+        /// ignores a carriage return at the end of a line when comparing lines
+        #[arg(long)]
+        strip_trailing_cr: bool,
+
+        /// ignores the case of letters when comparing lines
+        #[arg(short = 'i', long)]
+        ignore_case: bool,
+
+        /// ignores changes where every line matches the pattern. can be given
+        /// more than once
+        #[arg(short = 'I', long, value_name = "REGEX", value_parser = regex::Regex::new)]
+        ignore_matching_lines: Vec<regex::Regex>,
+
+        /// rewrites each line with a s/REGEX/REPLACEMENT/ rule before
+        /// comparing. a trailing g replaces every match. groups are referenced
+        /// with $1. can be given more than once and are applied in order
+        #[arg(long, value_name = "RULE", value_parser = normalize::Rule::parse)]
+        normalize: Vec<normalize::Rule>,
+
+        /// the number of unchanged lines to show around each change for the
+        /// unified format
+        #[arg(short = 'U', long, default_value_t = 3)]
+        context: usize,
+
+        /// compares the files of two directories by their relative path as a
+        /// single unified diff. the unified format is always used so --format
+        /// is ignored
+        #[arg(short, long)]
+        recursive: bool,
+
+        /// only reports if the inputs differ without showing the differences
+        #[arg(short = 'q', long)]
+        brief: bool,
+
+        /// the file to write the differences to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// shows blocks of lines that were moved separately from lines that
+        /// were deleted or inserted. leading and trailing whitespace is
+        /// ignored when comparing the lines
+        #[arg(long)]
+        moved: bool,
+
+        /// the minimum number of lines in a block for it to count as moved
+        #[arg(long, default_value_t = 2, requires("moved"))]
+        moved_min_lines: usize,
+    },
+
+    /// compares two JSON documents by structure listing each value that was
+    /// added, removed or replaced
+    Json {
+        /// the document you want to convert from
+        from_file: PathBuf,
+
+        /// the document you want to convert to
+        to_file: PathBuf,
+
+        /// the format to output the changes in
+        #[arg(long, value_enum, default_value_t = JsonFormat::Text)]
+        format: JsonFormat,
+
+        /// only reports if the inputs differ without showing the differences
+        #[arg(short = 'q', long)]
+        brief: bool,
+
+        /// the file to write the changes to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+
+    /// compares two CSV files by row listing the rows that were added or
+    /// removed and the cells of rows that were modified
+    Csv {
+        /// the file you want to convert from
+        from_file: PathBuf,
+
+        /// the file you want to convert to
+        to_file: PathBuf,
+
+        /// the name of a column used to match up rows. can be given more than
+        /// once
+        #[arg(short, long)]
+        key: Vec<String>,
+
+        /// the character that separates the cells
+        #[arg(short, long, default_value_t = ',')]
+        delimiter: char,
+
+        /// only reports if the inputs differ without showing the differences
+        #[arg(short = 'q', long)]
+        brief: bool,
+
+        /// the file to write the changes to instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
 }
 
-fn get_char_vec(given: &str) -> Vec<char> {
-    let mut rtn = Vec::with_capacity(given.len());
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiffMode {
+    /// compares each line
+    Line,
 
-    for ch in given.chars() {
-        rtn.push(ch);
-    }
+    /// compares the identifiers, numbers, strings, punctuation and
+    /// whitespace of source code showing the changes within the text
+    Token,
 
-    rtn
+    /// compares the words separated by whitespace or matched by
+    /// --word-regex showing the changes within the text
+    Word,
+
+    /// compares each user perceived character (extended grapheme cluster)
+    /// showing the changes within the text
+    Grapheme,
+
+    /// compares each unicode scalar value showing the changes within the
+    /// text
+    Char,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum DiffFormat {
+    /// lists each line with a marker for lines that are deleted or inserted
+    Text,
+
+    /// a standalone HTML page with side by side and inline views
+    Html,
+
+    /// only the changed lines with a few lines of context around them
+    Unified,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum JsonFormat {
+    /// lists each change with the JSON pointer of the value that changed
+    Text,
+
+    /// a JSON patch (RFC 6902) that converts from into to
+    Patch,
+}
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    /// the text grid shown in the writeup
+    Ascii,
+
+    /// an SVG image of the grid, every point explored for each depth and the
+    /// final path
+    Svg,
+
+    /// a Graphviz DOT graph of every point explored for each depth linked to
+    /// the point of the previous depth it came from
+    Dot,
+}
+
+fn get_char_vec(given: &str, mode: CharMode) -> Vec<&str> {
+    match mode {
+        CharMode::Grapheme => given.graphemes(true).collect(),
+        CharMode::Char => given.char_indices()
+            .map(|(index, ch)| &given[index..index + ch.len_utf8()])
+            .collect(),
+    }
 }
 
 fn usize_len(given: usize) -> usize {
@@ -57,121 +361,411 @@ fn usize_len(given: usize) -> usize {
     }
 }
 
-fn main() {
-    let args = AppArgs::parse();
+/// renders the alignment with a marker for the values that are deleted or
+/// inserted
+///
+/// values that are part of a move are marked with `<` where they were moved
+/// from and `>` where they were moved to. the rows in `suppressed` are changes
+/// that are ignored and are shown as unchanged.
+fn render_alignment<'a, T, I>(alignment: I, moves: &[moved::Move], suppressed: &[bool]) -> String
+where
+    T: std::fmt::Display + 'a,
+    I: Iterator<Item = edit::Aligned<'a, T>>
+{
+    let mut rtn = String::new();
+
+    for (index, aligned) in alignment.enumerate() {
+        if suppressed.get(index) == Some(&true) {
+            if let (Some((_, value)), _) | (None, Some((_, value))) = aligned {
+                writeln!(rtn, "   | {value}").unwrap();
+            }
 
-    let from_chars = get_char_vec(&args.from);
-    let to_chars = get_char_vec(&args.to);
+            continue;
+        }
 
-    println!("{} -> {}", args.from, args.to);
+        match aligned {
+            (Some((_, value)), Some(_)) => writeln!(rtn, "   | {value}").unwrap(),
+            (Some((x, value)), None) => if moves.iter().any(|found| found.contains_from(x)) {
+                writeln!(rtn, " < | {value}").unwrap()
+            } else {
+                writeln!(rtn, " - | {value}").unwrap()
+            },
+            (None, Some((y, value))) => if moves.iter().any(|found| found.contains_to(y)) {
+                writeln!(rtn, " > | {value}").unwrap()
+            } else {
+                writeln!(rtn, " + | {value}").unwrap()
+            },
+            (None, None) => {}
+        }
+    }
 
-    match args.op {
-        Operation::Base => {
-            let start = std::time::Instant::now();
+    rtn
+}
 
-            let result = shortest_edit::base(&from_chars, &to_chars);
+/// prints the k steps and trace of the given depth from printed
+fn print_depth(result: &shortest_edit::Steps, depth: usize, a_len: usize, b_len: usize) {
+    let mid = a_len + b_len;
+    let data = &result.depths[depth];
 
-            let duration = start.elapsed();
+    let snake_width = usize_len(result.max_snake);
+    let x_width = usize_len(a_len);
+    let y_width = usize_len(b_len);
+    let trace_width = usize_len(mid) + 1;
+    let depth_width = usize_len(result.depths.len()) + 1;
 
-            println!("edit distance: {result} {duration:?}");
-        }
-        Operation::Modified => {
-            let start = std::time::Instant::now();
+    let signed_depth = depth as i32;
 
-            let result = shortest_edit::modified(&from_chars, &to_chars);
+    println!("depth: {depth:depth_width$} | k: {} -> {}", -signed_depth, signed_depth);
 
-            let duration = start.elapsed();
+    for (step, k) in data.ks.iter().zip((-signed_depth..).step_by(2)) {
+        print!("    k: {:depth_width$}", k);
 
-            println!("edit distance: {result} {duration:?}");
+        match step.choice {
+            shortest_edit::KChoice::AtDepth => {
+                print!(" |  depth        ");
+            }
+            shortest_edit::KChoice::AtNegDepth => {
+                print!(" | -depth        ");
+            }
+            shortest_edit::KChoice::Greater => {
+                print!(" | k - 1 < k + 1 ");
+            }
+            shortest_edit::KChoice::Lesser => {
+                print!(" | k - 1 >= k + 1");
+            }
         }
-        Operation::Printed => {
-            let start = std::time::Instant::now();
 
-            let result = shortest_edit::printed(&from_chars, &to_chars);
+        println!(
+            " | x: {:x_width$} y: {:y_width$} | {:snake_width$}s snake | setting {:depth_width$} to {}",
+            step.x,
+            step.y,
+            step.snake,
+            k,
+            step.set
+        );
+    }
 
-            let duration = start.elapsed();
+    print!("trace:");
 
-            let mid = from_chars.len() + to_chars.len();
+    let signed_mid = mid as i32;
 
-            let snake_width = usize_len(result.max_snake);
-            let x_width = usize_len(from_chars.len());
-            let y_width = usize_len(to_chars.len());
-            let trace_width = usize_len(mid) + 1;
-            let depth_width = usize_len(result.depths.len()) + 1;
+    for v in -signed_mid..=signed_mid {
+        print!(" {v:trace_width$}");
+    }
 
-            for (depth, data) in result.depths.iter().enumerate() {
-                let signed_depth = depth as i32;
+    print!("\n      ");
 
-                println!("depth: {depth:depth_width$} | k: {} -> {}", -signed_depth, signed_depth);
+    for (index, v) in data.trace.iter().enumerate() {
+        if index < mid - depth || index > mid + depth {
+            print!(" {:trace_width$}", ' ');
+        } else {
+            print!(" {v:trace_width$}");
+        }
+    }
 
-                let mut k = -signed_depth;
+    println!();
+}
 
-                for step in &data.ks {
-                    print!("    k: {:depth_width$}", k);
+/// the commands available when stepping through the depths
+const STEP_HELP: &str = "\
+commands:
+    n, next     move to the next depth
+    b, back     move to the previous depth
+    j, jump N   move to depth N
+    k, show K   show the details of k K for the current depth
+    h, help     show this message
+    q, quit     stop stepping";
+
+/// interactively steps through each depth calculated by printed
+///
+/// the current depth is rendered with its k steps, trace and the moves
+/// explored so far every time the depth changes.
+fn step_through(from_chars: &[&str], to_chars: &[&str]) {
+    let result = shortest_edit::printed(from_chars, to_chars);
+    let last = result.depths.len() - 1;
+    let mut depth = 0;
+    let mut render = true;
+    let mut input = String::new();
+
+    println!("{STEP_HELP}");
+
+    loop {
+        if render {
+            println!();
+            print_depth(&result, depth, from_chars.len(), to_chars.len());
+            println!();
+            print!("{}", graph::explored(from_chars, to_chars, &result, depth));
+
+            if depth == last {
+                println!("edit distance: {last}");
+            }
+        }
 
-                    match step.choice {
-                        shortest_edit::KChoice::AtDepth => {
-                            print!(" |  depth        ");
-                        }
-                        shortest_edit::KChoice::AtNegDepth => {
-                            print!(" | -depth        ");
-                        }
-                        shortest_edit::KChoice::Greater => {
-                            print!(" | k - 1 < k + 1 ");
-                        }
-                        shortest_edit::KChoice::Lesser => {
-                            print!(" | k - 1 >= k + 1");
-                        }
-                    }
+        print!("> ");
+        std::io::stdout().flush().unwrap();
 
-                    println!(
-                        " | x: {:x_width$} y: {:y_width$} | {:snake_width$}s snake | setting {:depth_width$} to {}",
-                        step.x,
-                        step.y,
-                        step.snake,
-                        k,
-                        step.set
-                    );
+        input.clear();
 
-                    k += 1;
+        match std::io::stdin().read_line(&mut input) {
+            Ok(0) => break,
+            Ok(_) => {}
+            Err(err) => {
+                eprintln!("failed to read stdin: {err}");
+                std::process::exit(2);
+            }
+        }
+
+        let mut parts = input.split_whitespace();
+        let command = parts.next().unwrap_or("next");
+        let value = parts.next().map(str::parse::<isize>);
+
+        render = false;
+
+        match (command, value) {
+            ("n" | "next", None) => if depth < last {
+                depth += 1;
+                render = true;
+            } else {
+                println!("already at the last depth");
+            },
+            ("b" | "back", None) => if depth > 0 {
+                depth -= 1;
+                render = true;
+            } else {
+                println!("already at the first depth");
+            },
+            ("j" | "jump", Some(Ok(given))) => if given >= 0 && given as usize <= last {
+                depth = given as usize;
+                render = true;
+            } else {
+                println!("depth must be from 0 to {last}");
+            },
+            ("k" | "show", Some(Ok(given))) => {
+                let signed_depth = depth as isize;
+
+                if given < -signed_depth || given > signed_depth || (given + signed_depth) % 2 != 0 {
+                    println!("k must be from {} to {signed_depth} in steps of 2", -signed_depth);
+                } else if let Some(step) = result.depths[depth].ks.get(((given + signed_depth) / 2) as usize) {
+                    let from = match step.choice {
+                        shortest_edit::KChoice::AtNegDepth => "k + 1 since k is -depth",
+                        shortest_edit::KChoice::AtDepth => "k - 1 since k is depth",
+                        shortest_edit::KChoice::Greater => "k + 1 since k - 1 < k + 1",
+                        shortest_edit::KChoice::Lesser => "k - 1 since k - 1 >= k + 1",
+                    };
+
+                    println!("k: {given} at depth: {depth}");
+                    println!("    previous: {from}");
+                    println!("    start: ({},{})", step.x, step.y);
+                    println!("    snake: {}", step.snake);
+                    println!("    end: ({},{})", step.set, step.y + step.snake);
+                } else {
+                    println!("k {given} was not reached at depth {depth}");
                 }
+            }
+            ("h" | "help", None) => println!("{STEP_HELP}"),
+            ("q" | "quit", None) => break,
+            _ => println!("unknown command. {STEP_HELP}"),
+        }
+    }
+}
 
-                print!("trace:");
+/// prints a level from the linear printed along with all of its children
+///
+/// all positions are shown relative to the start of a and b instead of the
+/// slices of the level
+fn print_level(level: &shortest_edit::linear::Level, indent: usize) {
+    let prefix = "    ".repeat(indent);
+    let x_offset = level.left_index;
+    let y_offset = level.right_index;
+
+    println!(
+        "{prefix}level: a[{}..{}] b[{}..{}]",
+        x_offset,
+        x_offset + level.left_len,
+        y_offset,
+        y_offset + level.right_len
+    );
+
+    if level.passes.is_empty() {
+        for edit in &level.edits.ops {
+            match edit {
+                edit::Edit::Delete { pos } => println!("{prefix}    delete a[{pos}]"),
+                edit::Edit::Insert { pos, value } => println!("{prefix}    insert b[{value}] at a[{pos}]"),
+            }
+        }
 
-                let signed_mid = mid as i32;
+        if level.edits.ops.is_empty() {
+            println!("{prefix}    no edits");
+        }
 
-                for v in -signed_mid..=signed_mid {
-                    print!(" {v:trace_width$}");
-                }
+        return;
+    }
 
-                print!("\n      ");
+    let k_width = usize_len(level.left_len.max(level.right_len)) + 1;
+
+    for pass in &level.passes {
+        let (direction, name) = if pass.forward {
+            ("forward", "g")
+        } else {
+            ("reverse", "p")
+        };
+
+        println!("{prefix}    h: {} {direction}", pass.h);
+
+        for step in &pass.ks {
+            println!(
+                "{prefix}        k: {:k_width$} | x: {} y: {} -> x: {} y: {} | {}s snake",
+                step.k,
+                x_offset + step.start.0,
+                y_offset + step.start.1,
+                x_offset + step.end.0,
+                y_offset + step.end.1,
+                step.snake,
+            );
+        }
 
-                for (index, v) in data.trace.iter().enumerate() {
-                    if index < mid - depth || index > mid + depth {
-                        print!(" {:trace_width$}", ' ');
-                    } else {
-                        print!(" {v:trace_width$}");
-                    }
-                }
+        print!("{prefix}        {name}:");
+
+        for value in &pass.frontier {
+            print!(" {value}");
+        }
+
+        println!();
+    }
+
+    if let Some(snake) = &level.snake {
+        println!(
+            "{prefix}    middle snake: d: {} | x: {} y: {} -> x: {} y: {}",
+            snake.d,
+            x_offset + snake.x,
+            y_offset + snake.y,
+            x_offset + snake.u,
+            y_offset + snake.v,
+        );
+    }
+
+    for child in &level.children {
+        print_level(child, indent + 1);
+    }
+}
+
+/// reads the contents of the file exiting if it fails
+fn read_file(path: &std::path::Path) -> String {
+    match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("failed to read {}: {err}", path.display());
+            std::process::exit(2);
+        }
+    }
+}
 
-                println!("");
+/// reads the bytes of the file exiting if it fails
+fn read_bytes(path: &std::path::Path) -> Vec<u8> {
+    match std::fs::read(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            eprintln!("failed to read {}: {err}", path.display());
+            std::process::exit(2);
+        }
+    }
+}
+
+/// reads and parses the JSON document exiting if it fails
+fn read_json(path: &std::path::Path) -> serde_json::Value {
+    match serde_json::from_str(&read_file(path)) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("failed to parse {}: {err}", path.display());
+            std::process::exit(2);
+        }
+    }
+}
+
+/// reads and parses the CSV file exiting if it fails
+fn read_table(path: &std::path::Path, delimiter: u8) -> table::Table {
+    match table::Table::parse(&read_file(path), delimiter) {
+        Ok(value) => value,
+        Err(err) => {
+            eprintln!("failed to parse {}: {err}", path.display());
+            std::process::exit(2);
+        }
+    }
+}
+
+/// writes the given string to the file if provided otherwise stdout
+fn write_output(output: Option<PathBuf>, given: &str) {
+    if let Some(path) = output {
+        if let Err(err) = std::fs::write(&path, given) {
+            eprintln!("failed to write {}: {err}", path.display());
+            std::process::exit(2);
+        }
+    } else {
+        print!("{given}");
+    }
+}
+
+/// the exit status used by the commands that compare inputs
+///
+/// 0 if the inputs are the same and 1 if they differ. errors exit with 2
+fn exit_code(differ: bool) -> ExitCode {
+    if differ {
+        ExitCode::from(1)
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn main() -> ExitCode {
+    let args = AppArgs::parse();
+    let mut differ = false;
+
+    let from_chars = get_char_vec(&args.from, args.units);
+    let to_chars = get_char_vec(&args.to, args.units);
+
+    let quiet = matches!(
+        args.op,
+        Operation::Diff { .. }
+            | Operation::Json { .. }
+            | Operation::Csv { .. }
+            | Operation::Explain { .. }
+            | Operation::Blame { .. }
+            | Operation::Graph { format: GraphFormat::Svg | GraphFormat::Dot, .. }
+    );
+
+    if !quiet {
+        println!("{} -> {}", args.from, args.to);
+    }
+
+    match args.op {
+        Operation::Base => {
+            let result = shortest_edit::base(&from_chars, &to_chars);
+
+            println!("edit distance: {result}");
+        }
+        Operation::Modified => {
+            let result = shortest_edit::modified(&from_chars, &to_chars);
+
+            println!("edit distance: {result}");
+        }
+        Operation::Printed => {
+            let result = shortest_edit::printed(&from_chars, &to_chars);
+
+            for depth in 0..result.depths.len() {
+                print_depth(&result, depth, from_chars.len(), to_chars.len());
             }
 
-            println!("edit distance: {} {duration:?}", result.depths.len() - 1);
+            println!("edit distance: {}", result.depths.len() - 1);
         }
         Operation::Traced => {
-            let start = std::time::Instant::now();
-
             let result = shortest_edit::operations(&from_chars, &to_chars);
 
-            let duration = start.elapsed();
-
             let mid = from_chars.len() + to_chars.len();
             let trace_len = 2 * mid + 1;
             let depth_width = usize_len(result.edits.len());
             let trace_width = usize_len(mid) + 2;
 
-            println!("inserts: {} deletes: {} {duration:?}", result.inserts, result.deletes);
+            println!("inserts: {} deletes: {}", result.inserts, result.deletes);
 
             print!("{} index |", " ".repeat(depth_width + 1));
 
@@ -190,14 +784,14 @@ fn main() {
             print!("\ndepth: {:depth_width$} |", 0);
 
             for index in 0..trace_len {
-                if index < mid || index > mid {
+                if index != mid {
                     print!(" {:trace_width$}", ' ');
                 } else {
                     print!(" {:trace_width$}", 0);
                 }
             }
 
-            let mut mod_check = if mid % 2 == 0 { 0 } else { 1 };
+            let mut mod_check = mid % 2;
             let mut prev_k = mid;
 
             for (depth, step) in result.edits.iter().enumerate() {
@@ -234,63 +828,421 @@ fn main() {
                 mod_check ^= 1;
             }
 
-            println!("");
+            println!();
         }
         Operation::Operations => {
-            let start = std::time::Instant::now();
+            let result = shortest_edit::operations(&from_chars, &to_chars);
+
+            println!("inserts: {} deletes: {}", result.inserts, result.deletes);
+
+            let alignment = edit::Alignment::new(
+                &from_chars,
+                &to_chars,
+                result.edits.iter().map(|traced| traced.edit)
+            );
+
+            print!("{}", render_alignment(alignment, &[], &[]));
+        }
+        Operation::Dp => {
+            let result = shortest_edit::dp::operations(&from_chars, &to_chars);
+
+            println!("inserts: {} deletes: {}", result.inserts, result.deletes);
+
+            let alignment = edit::Alignment::new(&from_chars, &to_chars, result.ops);
+
+            print!("{}", render_alignment(alignment, &[], &[]));
+        }
+        Operation::Graph { path, snakes, format, output } => {
+            let rendered = match format {
+                GraphFormat::Ascii => if path {
+                    let points = graph::path_points(shortest_edit::alignment(&from_chars, &to_chars));
+
+                    graph::grid(&from_chars, &to_chars, Some(&points), snakes)
+                } else {
+                    graph::grid(&from_chars, &to_chars, None, false)
+                },
+                GraphFormat::Svg => {
+                    let steps = shortest_edit::printed(&from_chars, &to_chars);
+                    let points = graph::path_points(shortest_edit::alignment(&from_chars, &to_chars));
+
+                    graph::svg::render(&from_chars, &to_chars, &steps, &points)
+                }
+                GraphFormat::Dot => {
+                    let steps = shortest_edit::printed(&from_chars, &to_chars);
+                    let result = shortest_edit::operations(&from_chars, &to_chars);
+                    let mid = (from_chars.len() + to_chars.len()) as isize;
+                    let path: Vec<isize> = std::iter::once(0)
+                        .chain(result.edits.iter().map(|traced| traced.k as isize - mid))
+                        .collect();
+
+                    graph::dot::render(&steps, &path)
+                }
+            };
+
+            write_output(output, &rendered);
+        }
+        Operation::Step => {
+            step_through(&from_chars, &to_chars);
+        }
+        Operation::LinearPrinted => {
+            let result = shortest_edit::linear::printed(&from_chars, &to_chars, 0, 0);
+
+            print_level(&result, 0);
+
+            println!("inserts: {} deletes: {}", result.edits.inserts, result.edits.deletes);
+
+            let alignment = edit::Alignment::new(&from_chars, &to_chars, result.edits.ops);
+
+            print!("{}", render_alignment(alignment, &[], &[]));
+        }
+        Operation::Path => {
+            let segments = shortest_edit::path(&from_chars, &to_chars);
 
+            for segment in &segments {
+                let (x, y) = segment.start;
+                let (u, v) = segment.end;
+
+                match segment.edit {
+                    Some(edit::Edit::Delete { pos }) => {
+                        println!("({x},{y}) -> ({u},{v}) delete {}", from_chars[pos]);
+                    }
+                    Some(edit::Edit::Insert { value, .. }) => {
+                        println!("({x},{y}) -> ({u},{v}) insert {}", to_chars[value]);
+                    }
+                    None => {
+                        let kept = from_chars[x..u].concat();
+
+                        println!("({x},{y}) -> ({u},{v}) keep {kept}");
+                    }
+                }
+            }
+
+            println!();
+            print!("{}", graph::condensed(&segments));
+        }
+        Operation::Position { reverse, snap } => {
             let result = shortest_edit::operations(&from_chars, &to_chars);
+            let map = position::PositionMap::new(&from_chars, &to_chars, result.edits.iter().map(|traced| traced.edit));
+            let (given, other, name, other_name) = if reverse {
+                (&to_chars, &from_chars, "b", "a")
+            } else {
+                (&from_chars, &to_chars, "a", "b")
+            };
+
+            for index in 0..=given.len() {
+                let found = if reverse {
+                    map.to_a(index, snap)
+                } else {
+                    map.to_b(index, snap)
+                };
+                let value = given.get(index).map(|value| format!(" {value}")).unwrap_or_default();
 
-            let duration = start.elapsed();
+                match found {
+                    Some(found) => {
+                        let other_value = other.get(found).map(|value| format!(" {value}")).unwrap_or_default();
 
-            println!("inserts: {} deletes: {} {duration:?}", result.inserts, result.deletes);
+                        println!("{name}[{index}]{value} -> {other_name}[{found}]{other_value}");
+                    }
+                    None => println!("{name}[{index}]{value} -> none"),
+                }
+            }
+        }
+        Operation::Explain { output } => {
+            write_output(output, &explain::render(&from_chars, &to_chars));
+        }
+        Operation::Blame { files } => {
+            let contents: Vec<String> = files.iter()
+                .map(|path| read_file(path))
+                .collect();
+            let versions: Vec<Vec<&str>> = contents.iter()
+                .map(|text| text.lines().collect())
+                .collect();
+
+            let origins = annotate::annotate(&versions);
+            let version_width = usize_len(files.len() - 1);
+
+            if let Some(last) = versions.last() {
+                for (line, origin) in last.iter().zip(origins) {
+                    println!("{origin:version_width$} | {line}");
+                }
+            }
+        }
+        Operation::Diff {
+            from_file,
+            to_file,
+            mode,
+            format,
+            word_diff,
+            word_regex,
+            hex,
+            ignore_all_space,
+            ignore_space_change,
+            ignore_blank_lines,
+            strip_trailing_cr,
+            ignore_case,
+            ignore_matching_lines,
+            normalize,
+            context,
+            recursive,
+            brief,
+            output,
+            moved,
+            moved_min_lines,
+        } => {
+            let options = normalize::Options {
+                ignore_all_space,
+                ignore_space_change,
+                ignore_blank_lines,
+                strip_trailing_cr,
+                ignore_case,
+                ignore_matching_lines,
+                rules: normalize,
+            };
+            let ignoring = ignore_all_space
+                || ignore_space_change
+                || strip_trailing_cr
+                || ignore_case
+                || options.ignores_lines()
+                || !options.rules.is_empty();
+
+            if (mode != DiffMode::Line || hex) && ignoring {
+                eprintln!("-w, -b, -B, -i, -I, --strip-trailing-cr and --normalize are only available for the line mode");
+                std::process::exit(2);
+            }
+
+            if hex && (mode != DiffMode::Line || format != DiffFormat::Text || moved) {
+                eprintln!("--hex can not be used with --mode, --format or --moved");
+                std::process::exit(2);
+            }
 
-            let mut from_index = 0;
-            let mut to_index = 0;
+            if mode != DiffMode::Line && (moved || format != DiffFormat::Text) {
+                eprintln!("--moved and --format html or unified are only available for the line mode");
+                std::process::exit(2);
+            }
 
-            for traced in result.edits {
-                match traced.edit {
-                    edit::Edit::Delete { pos } => {
-                        if from_index != pos {
-                            while from_index < pos {
-                                println!("   | {}", from_chars[from_index]);
-                                from_index += 1;
-                                to_index += 1;
-                            }
-                        }
+            if recursive && (mode != DiffMode::Line || hex || moved || format == DiffFormat::Html) {
+                eprintln!("-r can not be used with --mode, --hex, --moved or --format html");
+                std::process::exit(2);
+            }
+
+            if mode != DiffMode::Word && word_regex.is_some() {
+                eprintln!("--word-regex is only available for the word mode");
+                std::process::exit(2);
+            }
+
+            if recursive {
+                let (Some(from_dir), Some(to_dir)) = (from_file, to_file) else {
+                    eprintln!("-r needs two directories to compare");
+                    std::process::exit(2);
+                };
+
+                if !from_dir.is_dir() || !to_dir.is_dir() {
+                    eprintln!("-r needs two directories to compare");
+                    std::process::exit(2);
+                }
+
+                let (rendered, found, trouble) = dir::diff(&from_dir, &to_dir, &options, context, brief);
+
+                differ = found;
+
+                write_output(output, &rendered);
+
+                if trouble {
+                    std::process::exit(2);
+                }
+            } else {
+                let (from_name, from_bytes, to_name, to_bytes) = match (from_file, to_file) {
+                    (Some(from_file), Some(to_file)) => (
+                        from_file.display().to_string(),
+                        read_bytes(&from_file),
+                        to_file.display().to_string(),
+                        read_bytes(&to_file),
+                    ),
+                    _ => (String::from("from"), args.from.clone().into_bytes(), String::from("to"), args.to.clone().into_bytes()),
+                };
+
+                let binary = from_bytes.contains(&0) || to_bytes.contains(&0);
+                let from_text = String::from_utf8_lossy(&from_bytes);
+                let to_text = String::from_utf8_lossy(&to_bytes);
+                let by_line = mode == DiffMode::Line && !hex && !binary;
+                let (from_lines, to_lines) = if by_line {
+                    (options.lines(&from_text), options.lines(&to_text))
+                } else {
+                    (Vec::new(), Vec::new())
+                };
+
+                differ = if by_line {
+                    !options.same(&from_lines, &to_lines)
+                } else {
+                    from_bytes != to_bytes
+                };
+
+                if brief {
+                    if differ {
+                        write_output(output, &format!("Files {from_name} and {to_name} differ\n"));
+                    }
+                } else if hex {
+                    if differ {
+                        let alignment = shortest_edit::linear::alignment(&from_bytes, &to_bytes);
 
-                        println!(" - | {}", from_chars[from_index]);
-                        from_index += 1;
+                        write_output(output, &hex::render(alignment));
                     }
-                    edit::Edit::Insert { pos, value } => {
-                        if from_index != pos {
-                            while from_index < pos {
-                                println!("   | {}", from_chars[from_index]);
-                                from_index += 1;
-                                to_index += 1;
+                } else if binary {
+                    if differ {
+                        write_output(output, &format!("Binary files {from_name} and {to_name} differ\n"));
+                    }
+                } else {
+                    match mode {
+                        DiffMode::Line => {
+                            let edits = shortest_edit::linear::edits(&from_lines, &to_lines);
+                            let moves = if moved {
+                                moved::detect(&from_lines, &to_lines, &edits, moved_min_lines, |line| line.key.trim())
+                            } else {
+                                Vec::new()
+                            };
+                            let alignment: Vec<edit::Aligned<normalize::Line>> = edit::Alignment::new(&from_lines, &to_lines, edits).collect();
+                            let suppressed = normalize::suppressed(&alignment);
+
+                            match format {
+                                DiffFormat::Text => {
+                                    write_output(output, &render_alignment(alignment.into_iter(), &moves, &suppressed));
+                                }
+                                DiffFormat::Unified => if differ {
+                                    let rendered = format!(
+                                        "--- {from_name}\n+++ {to_name}\n{}",
+                                        unified::render(
+                                            &alignment,
+                                            &suppressed,
+                                            context,
+                                            from_text.ends_with('\n'),
+                                            to_text.ends_with('\n')
+                                        )
+                                    );
+
+                                    write_output(output, &rendered);
+                                },
+                                DiffFormat::Html => {
+                                    let alignment = alignment.into_iter()
+                                        .map(|(a, b)| (a.map(|(x, line)| (x, &line.text)), b.map(|(y, line)| (y, &line.text))));
+                                    let rendered = html::render(&from_name, &to_name, alignment, &moves, &suppressed);
+
+                                    write_output(output, &rendered);
+                                }
                             }
                         }
+                        DiffMode::Token => {
+                            let from_tokens = token::tokenize(&from_text);
+                            let to_tokens = token::tokenize(&to_text);
+                            let alignment = shortest_edit::linear::alignment(&from_tokens, &to_tokens);
 
-                        println!(" + | {}", to_chars[value]);
-                        to_index += 1;
+                            write_output(output, &inline::render(alignment, word_diff));
+                        }
+                        DiffMode::Word => {
+                            let rendered = match word_regex {
+                                Some(pattern) => {
+                                    let pattern = match regex::Regex::new(&pattern) {
+                                        Ok(compiled) => compiled,
+                                        Err(err) => {
+                                            eprintln!("invalid word regex: {err}");
+                                            std::process::exit(2);
+                                        }
+                                    };
+                                    let from_pieces = token::matches(&from_text, &pattern);
+                                    let to_pieces = token::matches(&to_text, &pattern);
+
+                                    inline::render(shortest_edit::linear::alignment(&from_pieces, &to_pieces), word_diff)
+                                }
+                                None => {
+                                    let from_words = token::words(&from_text);
+                                    let to_words = token::words(&to_text);
+
+                                    inline::render(shortest_edit::linear::alignment(&from_words, &to_words), word_diff)
+                                }
+                            };
+
+                            write_output(output, &rendered);
+                        }
+                        DiffMode::Grapheme | DiffMode::Char => {
+                            let char_mode = if mode == DiffMode::Grapheme {
+                                CharMode::Grapheme
+                            } else {
+                                CharMode::Char
+                            };
+                            let from_chars = get_char_vec(&from_text, char_mode);
+                            let to_chars = get_char_vec(&to_text, char_mode);
+                            let alignment = shortest_edit::linear::alignment(&from_chars, &to_chars);
+
+                            write_output(output, &inline::render(alignment, word_diff));
+                        }
                     }
                 }
             }
+        }
+        Operation::Json { from_file, to_file, format, brief, output } => {
+            let from_value = read_json(&from_file);
+            let to_value = read_json(&to_file);
+
+            differ = from_value != to_value;
 
-            if from_index < from_chars.len() {
-                while from_index < from_chars.len() {
-                    println!("   | {}", from_chars[from_index]);
-                    from_index += 1;
-                    to_index += 1;
+            if brief {
+                if differ {
+                    write_output(output, &format!("Files {} and {} differ\n", from_file.display(), to_file.display()));
                 }
+            } else {
+                let changes = json::diff(&from_value, &to_value);
+
+                match format {
+                    JsonFormat::Text => {
+                        write_output(output, &json::render(&changes));
+                    }
+                    JsonFormat::Patch => {
+                        let rendered = serde_json::to_string_pretty(&json::patch(&changes)).unwrap();
+
+                        write_output(output, &format!("{rendered}\n"));
+                    }
+                }
+            }
+        }
+        Operation::Csv { from_file, to_file, key, delimiter, brief, output } => {
+            if !delimiter.is_ascii() {
+                eprintln!("delimiter must be an ASCII character");
+                std::process::exit(2);
+            }
+
+            let from_table = read_table(&from_file, delimiter as u8);
+            let to_table = read_table(&to_file, delimiter as u8);
+
+            if let Err(err) = table::check_keys(&from_table, &to_table, &key) {
+                eprintln!("{err}");
+                std::process::exit(2);
             }
 
-            if to_index < to_chars.len() {
-                while to_index < to_chars.len() {
-                    println!("++ | {}", to_chars[to_index]);
-                    to_index += 1;
+            if brief {
+                differ = !table::same(&from_table, &to_table);
+
+                if differ {
+                    write_output(output, &format!("Files {} and {} differ\n", from_file.display(), to_file.display()));
+                }
+            } else {
+                match table::diff(&from_table, &to_table, &key) {
+                    Ok(result) => {
+                        differ = !result.rows.is_empty();
+
+                        write_output(output, &table::render(&result));
+                    }
+                    Err(err) => {
+                        eprintln!("{err}");
+                        std::process::exit(2);
+                    }
                 }
             }
         }
+        Operation::Kgraph => {
+            let result = shortest_edit::printed(&from_chars, &to_chars);
+
+            print!("{}", graph::kgraph::render(&result));
+        }
     }
+
+    exit_code(differ)
 }
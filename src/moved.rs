@@ -0,0 +1,163 @@
+//! finds blocks of values that were moved instead of deleted and inserted
+//!
+//! the algorithm only knows about deletes and inserts so a block of values
+//! that is moved shows up as a delete in one place and an insert in another.
+//! this pairs the deleted values with the inserted values that are the same
+//! and reports them as a single move.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::edit::Edit;
+
+/// a block of values that was moved
+///
+/// `from` is the start of the block in a and `to` is the start of the block in
+/// b with both blocks being `len` values long
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Move {
+    pub from: usize,
+    pub to: usize,
+    pub len: usize,
+}
+
+impl Move {
+    /// checks if the position in a is part of the block that was moved
+    pub fn contains_from(&self, pos: usize) -> bool {
+        pos >= self.from && pos < self.from + self.len
+    }
+
+    /// checks if the position in b is part of the block that it was moved to
+    pub fn contains_to(&self, pos: usize) -> bool {
+        pos >= self.to && pos < self.to + self.len
+    }
+}
+
+/// finds the moves in the edits comparing values by the key given for them
+///
+/// the inserted values are indexed by their key so each deleted value is only
+/// checked against the inserted values with the same key that have not been
+/// paired yet. the longest run of consecutive deletes that match consecutive
+/// inserts is taken and runs shorter than `min_len` are ignored.
+pub fn detect<'a, T, K, F>(a: &'a [T], b: &'a [T], edits: &[Edit], min_len: usize, key: F) -> Vec<Move>
+where
+    K: Hash + Eq,
+    F: Fn(&'a T) -> K
+{
+    let mut a_keys: Vec<Option<K>> = a.iter().map(|_| None).collect();
+    let mut b_keys: Vec<Option<K>> = b.iter().map(|_| None).collect();
+
+    for edit in edits {
+        match *edit {
+            Edit::Delete { pos } => a_keys[pos] = Some(key(&a[pos])),
+            Edit::Insert { value, .. } => b_keys[value] = Some(key(&b[value])),
+        }
+    }
+
+    let mut index: HashMap<&K, Vec<usize>> = HashMap::new();
+
+    for (to, found) in b_keys.iter().enumerate() {
+        if let Some(found) = found {
+            index.entry(found).or_default().push(to);
+        }
+    }
+
+    let mut deleted: Vec<bool> = a_keys.iter().map(Option::is_some).collect();
+    let mut inserted: Vec<bool> = b_keys.iter().map(Option::is_some).collect();
+    let min_len = min_len.max(1);
+    let mut rtn = Vec::new();
+    let mut from = 0;
+
+    while from < a.len() {
+        let Some(candidates) = a_keys[from].as_ref().filter(|_| deleted[from]).and_then(|found| index.get(found)) else {
+            from += 1;
+            continue;
+        };
+
+        let mut best: Option<Move> = None;
+
+        for &to in candidates {
+            let mut len = 0;
+
+            while from + len < a.len()
+                && to + len < b.len()
+                && deleted[from + len]
+                && inserted[to + len]
+                && a_keys[from + len] == b_keys[to + len]
+            {
+                len += 1;
+            }
+
+            if len >= min_len && best.is_none_or(|found| len > found.len) {
+                best = Some(Move { from, to, len });
+            }
+        }
+
+        if let Some(found) = best {
+            deleted[found.from..found.from + found.len].fill(false);
+            inserted[found.to..found.to + found.len].fill(false);
+
+            rtn.push(found);
+            from += found.len;
+        } else {
+            from += 1;
+        }
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortest_edit;
+
+    fn moves(a: &[&str], b: &[&str], min_len: usize) -> Vec<Move> {
+        let result = shortest_edit::operations(a, b);
+        let edits: Vec<Edit> = result.edits.iter().map(|traced| traced.edit).collect();
+
+        detect(a, b, &edits, min_len, |line| line.trim())
+    }
+
+    #[test]
+    fn finds_a_moved_block() {
+        let a = ["x", "y", "a", "b", "c", "d"];
+        let b = ["a", "b", "c", "d", "x", "y"];
+
+        assert_eq!(moves(&a, &b, 2), vec![Move { from: 0, to: 4, len: 2 }]);
+    }
+
+    #[test]
+    fn ignores_changes_that_are_not_moves() {
+        let a = ["a", "x", "y", "b"];
+        let b = ["a", "z", "w", "b"];
+
+        assert_eq!(moves(&a, &b, 1), Vec::new());
+        assert_eq!(moves(&a, &a, 1), Vec::new());
+    }
+
+    #[test]
+    fn skips_blocks_shorter_than_min_len() {
+        let a = ["x", "y", "a", "b", "c", "d"];
+        let b = ["a", "b", "c", "d", "x", "y"];
+
+        assert_eq!(moves(&a, &b, 3), Vec::new());
+        assert_eq!(moves(&a, &b, 0), vec![Move { from: 0, to: 4, len: 2 }]);
+    }
+
+    #[test]
+    fn compares_values_by_key() {
+        let a = ["  x", "y", "a", "b", "c", "d"];
+        let b = ["a", "b", "c", "d", "x  ", "y"];
+
+        assert_eq!(moves(&a, &b, 2), vec![Move { from: 0, to: 4, len: 2 }]);
+    }
+
+    #[test]
+    fn takes_the_longest_run() {
+        let a = ["x", "y", "z", "a", "b", "c", "d", "e"];
+        let b = ["a", "b", "x", "c", "d", "e", "x", "y", "z"];
+
+        assert_eq!(moves(&a, &b, 2), vec![Move { from: 0, to: 6, len: 3 }]);
+    }
+}
@@ -0,0 +1,280 @@
+//! compares lines while ignoring differences in whitespace or case
+//!
+//! each line is paired with a key that has the ignored differences removed
+//! and any rewrite rules applied. lines are compared by their key but still
+//! show their original text so the output of a diff is unchanged apart from
+//! which lines are matched up.
+
+use std::fmt::Display;
+
+use regex::Regex;
+
+use crate::edit::{Aligned, Chunk, Chunks};
+use crate::shortest_edit;
+
+/// a `s/REGEX/REPLACEMENT/` rule used to rewrite the key of a line
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pattern: Regex,
+    replacement: String,
+    global: bool,
+}
+
+impl Rule {
+    /// parses a rule in the form `s/REGEX/REPLACEMENT/` with an optional `g`
+    /// at the end to replace every match instead of the first one
+    ///
+    /// any character can be used in place of `/` and can be escaped with a
+    /// backslash. groups are referenced in the replacement with `$1` or
+    /// `${name}`.
+    pub fn parse(given: &str) -> Result<Self, String> {
+        let mut chars = given.chars();
+
+        let (Some('s'), Some(delimiter)) = (chars.next(), chars.next()) else {
+            return Err(String::from("expected a rule in the form s/REGEX/REPLACEMENT/"));
+        };
+
+        let mut parts = vec![String::new()];
+        let mut escaped = false;
+
+        for ch in chars {
+            if escaped {
+                if ch != delimiter {
+                    parts.last_mut().unwrap().push('\\');
+                }
+
+                parts.last_mut().unwrap().push(ch);
+                escaped = false;
+            } else if ch == '\\' {
+                escaped = true;
+            } else if ch == delimiter {
+                parts.push(String::new());
+            } else {
+                parts.last_mut().unwrap().push(ch);
+            }
+        }
+
+        if escaped {
+            parts.last_mut().unwrap().push('\\');
+        }
+
+        let [pattern, replacement, flags] = <[String; 3]>::try_from(parts)
+            .map_err(|_| String::from("expected a rule in the form s/REGEX/REPLACEMENT/"))?;
+
+        let global = match flags.as_str() {
+            "" => false,
+            "g" => true,
+            _ => return Err(format!("unknown flags \"{flags}\"")),
+        };
+
+        let pattern = Regex::new(&pattern).map_err(|err| err.to_string())?;
+
+        Ok(Rule { pattern, replacement, global })
+    }
+
+    /// applies the rule to the line
+    pub fn apply(&self, line: &str) -> String {
+        if self.global {
+            self.pattern.replace_all(line, self.replacement.as_str()).into_owned()
+        } else {
+            self.pattern.replace(line, self.replacement.as_str()).into_owned()
+        }
+    }
+}
+
+/// the differences to ignore when comparing lines
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// ignores all whitespace
+    pub ignore_all_space: bool,
+    /// ignores changes in the amount of whitespace and whitespace at the end
+    /// of a line
+    pub ignore_space_change: bool,
+    /// ignores changes that only add or remove blank lines
+    pub ignore_blank_lines: bool,
+    /// ignores a carriage return at the end of a line
+    pub strip_trailing_cr: bool,
+    /// ignores the case of letters
+    pub ignore_case: bool,
+    /// ignores changes where every line matches one of the patterns
+    pub ignore_matching_lines: Vec<Regex>,
+    /// rewrites the key of each line in order before the other options
+    pub rules: Vec<Rule>,
+}
+
+/// a line along with the key it is compared by
+#[derive(Debug, Clone)]
+pub struct Line<'a> {
+    pub text: &'a str,
+    pub key: String,
+    /// if changes to the line can be ignored
+    pub ignored: bool,
+    /// if the line ends with a newline. the last line of a text without a
+    /// final newline is only equal to another line that is missing it too
+    pub newline: bool,
+}
+
+impl PartialEq for Line<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key && self.newline == other.newline
+    }
+}
+
+impl Display for Line<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl Options {
+    /// creates the key for the line
+    pub fn key(&self, line: &str) -> String {
+        let line = if self.strip_trailing_cr {
+            line.strip_suffix('\r').unwrap_or(line)
+        } else {
+            line
+        };
+        let mut rewritten = line.to_owned();
+
+        for rule in &self.rules {
+            rewritten = rule.apply(&rewritten);
+        }
+
+        let line = rewritten.as_str();
+
+        let mut rtn = if self.ignore_all_space {
+            line.chars().filter(|ch| !ch.is_whitespace()).collect()
+        } else if self.ignore_space_change {
+            line.split_whitespace().collect::<Vec<&str>>().join(" ")
+        } else {
+            line.to_owned()
+        };
+
+        if self.ignore_space_change && line.starts_with(char::is_whitespace) && !rtn.is_empty() {
+            rtn.insert(0, ' ');
+        }
+
+        if self.ignore_case {
+            rtn = rtn.to_lowercase();
+        }
+
+        rtn
+    }
+
+    /// splits the text into lines paired with their keys
+    ///
+    /// a missing newline at the end of the text is treated as whitespace so it
+    /// is only ignored along with changes in whitespace
+    pub fn lines<'a>(&self, text: &'a str) -> Vec<Line<'a>> {
+        let mut rtn: Vec<Line<'a>> = split_lines(text)
+            .map(|line| {
+                let key = self.key(line);
+                let ignored = (self.ignore_blank_lines && key.is_empty())
+                    || self.ignore_matching_lines.iter().any(|pattern| pattern.is_match(line));
+
+                Line { text: line, key, ignored, newline: true }
+            })
+            .collect();
+
+        if let Some(last) = rtn.last_mut() {
+            last.newline = text.ends_with('\n') || self.ignore_all_space || self.ignore_space_change;
+        }
+
+        rtn
+    }
+
+    /// checks if changes to some lines can be ignored
+    pub fn ignores_lines(&self) -> bool {
+        self.ignore_blank_lines || !self.ignore_matching_lines.is_empty()
+    }
+
+    /// checks if the lines are the same apart from changes that only add or
+    /// remove lines that are ignored
+    pub fn same(&self, a: &[Line], b: &[Line]) -> bool {
+        if !self.ignores_lines() {
+            return a == b;
+        }
+
+        let rows: Vec<Aligned<Line>> = shortest_edit::linear::alignment(a, b).collect();
+
+        rows.iter()
+            .zip(suppressed(&rows))
+            .all(|(aligned, hidden)| hidden || aligned.0.is_some() && aligned.1.is_some())
+    }
+}
+
+/// splits the text on each newline keeping any carriage returns
+pub fn split_lines(text: &str) -> impl Iterator<Item = &str> {
+    text.strip_suffix('\n')
+        .unwrap_or(text)
+        .split('\n')
+        .filter(move |_| !text.is_empty())
+}
+
+/// marks the rows of the changes that only add or remove lines that are
+/// ignored
+///
+/// the rows are still real edits so the line numbers of the rows after them
+/// are unchanged, they are only left out when deciding if the lines differ
+/// and which hunks to show
+pub fn suppressed(rows: &[Aligned<Line>]) -> Vec<bool> {
+    let mut rtn = Vec::with_capacity(rows.len());
+
+    for chunk in Chunks::new(rows.iter().copied()) {
+        match chunk {
+            Chunk::Same(..) => rtn.push(false),
+            Chunk::Change(deletes, inserts) => {
+                let all_ignored = deletes.iter().chain(&inserts).all(|(_, line)| line.ignored);
+
+                rtn.extend(std::iter::repeat_n(all_ignored, deletes.len() + inserts.len()));
+            }
+        }
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// parses the rule and applies it to the line
+    fn applied(rule: &str, line: &str) -> String {
+        Rule::parse(rule).unwrap().apply(line)
+    }
+
+    #[test]
+    fn rule_replaces_the_first_match() {
+        assert_eq!(applied("s/a/b/", "aaa"), "baa");
+        assert_eq!(applied("s/a/b/g", "aaa"), "bbb");
+        assert_eq!(applied("s/(?<n>[0-9]+)/<$n>/", "x 12"), "x <12>");
+    }
+
+    #[test]
+    fn rule_keeps_escapes_that_are_not_the_delimiter() {
+        assert_eq!(applied(r"s/\d+/N/g", "a1 b22"), "aN bN");
+        assert_eq!(applied(r"s/\./,/", "1.5"), "1,5");
+    }
+
+    #[test]
+    fn rule_escapes_the_delimiter() {
+        assert_eq!(applied(r"s/a\/b/c/", "a/b"), "c");
+        assert_eq!(applied(r"s|a/b|c\|d|", "a/b"), "c|d");
+        assert_eq!(applied("s#/tmp/[a-z]+#DIR#", "/tmp/abc/x"), "DIR/x");
+    }
+
+    #[test]
+    fn rule_errors() {
+        let expected = "expected a rule in the form s/REGEX/REPLACEMENT/";
+
+        assert_eq!(Rule::parse("").unwrap_err(), expected);
+        assert_eq!(Rule::parse("s").unwrap_err(), expected);
+        assert_eq!(Rule::parse("x/a/b/").unwrap_err(), expected);
+        assert_eq!(Rule::parse("s/a/b").unwrap_err(), expected);
+        assert_eq!(Rule::parse("s/a/b/g/").unwrap_err(), expected);
+        assert_eq!(Rule::parse(r"s/a/b\/").unwrap_err(), expected);
+        assert_eq!(Rule::parse("s/a/b/i").unwrap_err(), "unknown flags \"i\"");
+        assert_eq!(Rule::parse("s/a/b/\\").unwrap_err(), "unknown flags \"\\\"");
+        assert!(Rule::parse("s/(/b/").is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! maps positions between the old and new versions of a sequence
+//!
+//! built from the edits needed to convert a to b. unchanged values map
+//! directly to their position in the other sequence while deleted values of a
+//! and inserted values of b are snapped to a neighboring position.
+
+use crate::edit::{Alignment, Edit};
+
+/// how to map a position that does not exist in the other sequence
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum Snap {
+    /// the position just before where the value was removed or added
+    Left,
+    /// the position just after where the value was removed or added
+    Right,
+    /// do not map the position
+    None,
+}
+
+/// maps the positions of a to b and b to a
+///
+/// each entry is either `Ok` with the position in the other sequence or `Err`
+/// with the number of values in the other sequence that come before it
+pub struct PositionMap {
+    a_to_b: Vec<Result<usize, usize>>,
+    b_to_a: Vec<Result<usize, usize>>,
+}
+
+impl PositionMap {
+    pub fn new<T, I>(a: &[T], b: &[T], edits: I) -> Self
+    where
+        I: IntoIterator<Item = Edit>
+    {
+        let mut a_to_b = Vec::with_capacity(a.len());
+        let mut b_to_a = Vec::with_capacity(b.len());
+
+        for aligned in Alignment::new(a, b, edits) {
+            match aligned {
+                (Some(_), Some(_)) => {
+                    a_to_b.push(Ok(b_to_a.len()));
+                    b_to_a.push(Ok(a_to_b.len() - 1));
+                }
+                (Some(_), None) => a_to_b.push(Err(b_to_a.len())),
+                (None, Some(_)) => b_to_a.push(Err(a_to_b.len())),
+                (None, None) => {}
+            }
+        }
+
+        PositionMap { a_to_b, b_to_a }
+    }
+
+    /// maps the position in a to its position in b
+    ///
+    /// the length of a maps to the length of b so that the end of a sequence
+    /// can be mapped as well
+    pub fn to_b(&self, index: usize, snap: Snap) -> Option<usize> {
+        Self::lookup(&self.a_to_b, self.b_to_a.len(), index, snap)
+    }
+
+    /// maps the position in b to its position in a
+    ///
+    /// the length of b maps to the length of a so that the end of a sequence
+    /// can be mapped as well
+    pub fn to_a(&self, index: usize, snap: Snap) -> Option<usize> {
+        Self::lookup(&self.b_to_a, self.a_to_b.len(), index, snap)
+    }
+
+    fn lookup(map: &[Result<usize, usize>], other_len: usize, index: usize, snap: Snap) -> Option<usize> {
+        if index == map.len() {
+            return Some(other_len);
+        }
+
+        match (map.get(index)?, snap) {
+            (Ok(found), _) => Some(*found),
+            (Err(before), Snap::Left) => before.checked_sub(1),
+            (Err(before), Snap::Right) => Some(*before),
+            (Err(_), Snap::None) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortest_edit;
+
+    fn build(a: &str, b: &str) -> PositionMap {
+        let a: Vec<char> = a.chars().collect();
+        let b: Vec<char> = b.chars().collect();
+        let result = shortest_edit::operations(&a, &b);
+
+        PositionMap::new(&a, &b, result.edits.iter().map(|traced| traced.edit))
+    }
+
+    #[test]
+    fn unchanged_positions_map_directly() {
+        let map = build("abc", "abc");
+
+        for index in 0..=3 {
+            assert_eq!(map.to_b(index, Snap::None), Some(index));
+            assert_eq!(map.to_a(index, Snap::None), Some(index));
+        }
+    }
+
+    #[test]
+    fn positions_shift_past_inserts() {
+        let map = build("ac", "abbc");
+
+        assert_eq!(map.to_b(0, Snap::None), Some(0));
+        assert_eq!(map.to_b(1, Snap::None), Some(3));
+        assert_eq!(map.to_b(2, Snap::None), Some(4));
+
+        assert_eq!(map.to_a(1, Snap::None), None);
+        assert_eq!(map.to_a(2, Snap::Left), Some(0));
+        assert_eq!(map.to_a(2, Snap::Right), Some(1));
+    }
+
+    #[test]
+    fn positions_shift_past_deletes() {
+        let map = build("abbc", "ac");
+
+        assert_eq!(map.to_b(0, Snap::None), Some(0));
+        assert_eq!(map.to_b(3, Snap::None), Some(1));
+        assert_eq!(map.to_b(4, Snap::None), Some(2));
+
+        assert_eq!(map.to_b(1, Snap::None), None);
+        assert_eq!(map.to_b(1, Snap::Left), Some(0));
+        assert_eq!(map.to_b(2, Snap::Right), Some(1));
+
+        assert_eq!(map.to_a(1, Snap::None), Some(3));
+    }
+
+    #[test]
+    fn snapping_at_the_ends() {
+        let map = build("xab", "ab");
+
+        assert_eq!(map.to_b(0, Snap::Left), None);
+        assert_eq!(map.to_b(0, Snap::Right), Some(0));
+
+        let map = build("abx", "ab");
+
+        assert_eq!(map.to_b(2, Snap::Left), Some(1));
+        assert_eq!(map.to_b(2, Snap::Right), Some(2));
+    }
+
+    #[test]
+    fn out_of_range_positions_are_not_mapped() {
+        let map = build("ab", "abc");
+
+        assert_eq!(map.to_b(3, Snap::Right), None);
+        assert_eq!(map.to_a(4, Snap::Left), None);
+    }
+}
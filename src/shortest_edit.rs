@@ -4,9 +4,10 @@
 //! distance between two strings.
 
 use crate::array::{gi, si};
-use crate::edit::Edit;
+use crate::edit::{Alignment, Edit};
 
-//pub mod linear;
+pub mod dp;
+pub mod linear;
 
 /// the base form of the algorithm
 pub fn base<T>(a: &[T], b: &[T]) -> isize
@@ -16,6 +17,11 @@ where
     let a_len = a.len() as isize;
     let b_len = b.len() as isize;
     let max = a_len + b_len;
+
+    if max == 0 {
+        return 0;
+    }
+
     let mut values = vec![0isize; (2 * max + 1) as usize];
 
     for depth in 0..=max {
@@ -32,7 +38,7 @@ where
 
             //print!("    k: {k} x: {x} y: {y}");
 
-            while x < a_len && y < b_len && gi(&a, x) == gi(&b, y) {
+            while x < a_len && y < b_len && gi(a, x) == gi(b, y) {
                 x += 1;
                 y += 1;
             }
@@ -60,6 +66,11 @@ where
     T: PartialEq
 {
     let mid = a.len() + b.len();
+
+    if mid == 0 {
+        return 0;
+    }
+
     let mut values = vec![0usize; 2 * mid + 1];
 
     for depth in 0..=mid {
@@ -123,6 +134,17 @@ where
     T: PartialEq
 {
     let mid = a.len() + b.len();
+
+    if mid == 0 {
+        return Steps {
+            max_snake: 0,
+            depths: vec![DepthStep {
+                ks: vec![KStep { choice: KChoice::AtNegDepth, x: 0, y: 0, snake: 0, set: 0 }],
+                trace: vec![0],
+            }],
+        };
+    }
+
     let mut values = vec![0usize; 2 * mid + 1];
 
     let mut rtn = Steps {
@@ -196,6 +218,11 @@ where
     T: PartialEq
 {
     let mid = a.len() + b.len();
+
+    if mid == 0 {
+        return vec![vec![0]];
+    }
+
     let mut values = vec![0usize; 2 * mid + 1];
     let mut trace = Vec::new();
 
@@ -316,3 +343,185 @@ where
         edits,
     }
 }
+
+/// a single edit followed by the snake after it
+///
+/// the edit moves from `start` to `mid` and the snake moves diagonally from
+/// `mid` to `end`. the snake can be empty making `mid` and `end` the same. a
+/// snake at the very start of the path has no edit and `start` will be the
+/// same as `mid`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PathSegment {
+    pub edit: Option<Edit>,
+    pub start: (usize, usize),
+    pub mid: (usize, usize),
+    pub end: (usize, usize),
+}
+
+/// creates the condensed path from `(0,0)` to `(len(a),len(b))`
+///
+/// each edit from [`operations`] is combined with the snake that follows it
+pub fn path<T>(a: &[T], b: &[T]) -> Vec<PathSegment>
+where
+    T: PartialEq
+{
+    let mut rtn = Vec::new();
+    let result = operations(a, b);
+    let mut edits = result.edits.iter().map(|traced| traced.edit).peekable();
+    let mut x = 0;
+    let mut y = 0;
+
+    // moves diagonally until the position of the next edit or the end
+    let snake_end = |x: usize, y: usize, next: Option<&Edit>| {
+        let pos = match next {
+            Some(Edit::Delete { pos }) | Some(Edit::Insert { pos, .. }) => *pos,
+            None => a.len(),
+        };
+
+        (pos, y + (pos - x))
+    };
+
+    let end = snake_end(x, y, edits.peek());
+
+    if end != (x, y) {
+        rtn.push(PathSegment {
+            edit: None,
+            start: (x, y),
+            mid: (x, y),
+            end,
+        });
+
+        (x, y) = end;
+    }
+
+    while let Some(edit) = edits.next() {
+        let start = (x, y);
+
+        match edit {
+            Edit::Delete { .. } => x += 1,
+            Edit::Insert { .. } => y += 1,
+        }
+
+        let mid = (x, y);
+        let end = snake_end(x, y, edits.peek());
+
+        rtn.push(PathSegment {
+            edit: Some(edit),
+            start,
+            mid,
+            end,
+        });
+
+        (x, y) = end;
+    }
+
+    rtn
+}
+
+/// creates the full two column alignment of a and b
+///
+/// uses the edits from [`operations`] to pair each value of a with its value
+/// in b, or with nothing if it was deleted or inserted
+pub fn alignment<'a, T>(a: &'a [T], b: &'a [T]) -> Alignment<'a, T>
+where
+    T: PartialEq
+{
+    let result = operations(a, b);
+
+    Alignment::new(a, b, result.edits.into_iter().map(|traced| traced.edit))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortest_edit::dp::tests::inputs;
+
+    /// splits the string into its bytes
+    fn bytes(given: &str) -> Vec<u8> {
+        given.bytes().collect()
+    }
+
+    /// checks that the segments are connected from `(0,0)` to the end and that
+    /// each edit and snake moves the way it should
+    fn check_path(a: &[u8], b: &[u8], segments: &[PathSegment]) {
+        let mut at = (0, 0);
+
+        for (index, segment) in segments.iter().enumerate() {
+            assert_eq!(segment.start, at, "{a:?} -> {b:?}");
+
+            match segment.edit {
+                Some(Edit::Delete { .. }) => assert_eq!(segment.mid, (segment.start.0 + 1, segment.start.1)),
+                Some(Edit::Insert { .. }) => assert_eq!(segment.mid, (segment.start.0, segment.start.1 + 1)),
+                None => {
+                    assert_eq!(index, 0, "{a:?} -> {b:?}");
+                    assert_eq!(segment.start, segment.mid);
+                }
+            }
+
+            let (mid_x, mid_y) = segment.mid;
+            let (end_x, end_y) = segment.end;
+
+            assert_eq!(end_x - mid_x, end_y - mid_y, "{a:?} -> {b:?}");
+            assert_eq!(a[mid_x..end_x], b[mid_y..end_y], "{a:?} -> {b:?}");
+
+            at = segment.end;
+        }
+
+        assert_eq!(at, (a.len(), b.len()), "{a:?} -> {b:?}");
+    }
+
+    #[test]
+    fn path_starts_with_a_leading_snake() {
+        let a = bytes("abc");
+        let b = bytes("abd");
+        let segments = path(&a, &b);
+
+        assert_eq!(segments[0], PathSegment { edit: None, start: (0, 0), mid: (0, 0), end: (2, 2) });
+        assert_eq!(segments.len(), 3);
+
+        check_path(&a, &b, &segments);
+    }
+
+    #[test]
+    fn path_ends_with_an_empty_snake() {
+        let a = bytes("ab");
+        let b = bytes("abc");
+        let segments = path(&a, &b);
+
+        assert_eq!(segments.last(), Some(&PathSegment {
+            edit: Some(Edit::Insert { pos: 2, value: 2 }),
+            start: (2, 2),
+            mid: (2, 3),
+            end: (2, 3),
+        }));
+
+        check_path(&a, &b, &segments);
+    }
+
+    #[test]
+    fn path_of_identical_inputs_is_one_snake() {
+        let a = bytes("abc");
+
+        assert_eq!(path(&a, &a), [PathSegment { edit: None, start: (0, 0), mid: (0, 0), end: (3, 3) }]);
+    }
+
+    #[test]
+    fn path_of_empty_inputs() {
+        assert_eq!(path::<u8>(&[], &[]), []);
+
+        let b = bytes("ab");
+        let segments = path(&[], &b);
+
+        assert_eq!(segments.len(), 2);
+        assert!(segments.iter().all(|segment| matches!(segment.edit, Some(Edit::Insert { .. }))));
+
+        check_path(&[], &b, &segments);
+    }
+
+    #[test]
+    fn path_segments_are_connected() {
+        for (a, b) in inputs() {
+            check_path(&a, &b, &path(&a, &b));
+        }
+    }
+}
@@ -0,0 +1,176 @@
+//! classic dynamic programming form of the shortest edit problem
+//!
+//! builds the full `(N + 1) * (M + 1)` table of longest common subsequence
+//! lengths and walks it backwards to get the edits. this takes `O(NM)` time and
+//! space regardless of how similar the inputs are so it is only meant for small
+//! inputs. since it shares nothing with the Myers forms it can be used to check
+//! their results or when the inputs are tiny and the edit distance is large.
+
+use crate::edit::{Edit, TotalEdits};
+
+/// creates the longest common subsequence table for a and b
+///
+/// `table[x][y]` is the length of the longest common subsequence of `a[x..]`
+/// and `b[y..]`. building it from the end allows the edits to be walked from
+/// `(0,0)` forward.
+fn table<T>(a: &[T], b: &[T]) -> Vec<Vec<usize>>
+where
+    T: PartialEq
+{
+    let mut rtn = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+
+    for x in (0..a.len()).rev() {
+        for y in (0..b.len()).rev() {
+            rtn[x][y] = if a[x] == b[y] {
+                rtn[x + 1][y + 1] + 1
+            } else {
+                rtn[x + 1][y].max(rtn[x][y + 1])
+            };
+        }
+    }
+
+    rtn
+}
+
+/// returns the edit distance between a and b
+#[cfg(test)]
+pub fn distance<T>(a: &[T], b: &[T]) -> usize
+where
+    T: PartialEq
+{
+    let lcs = table(a, b)[0][0];
+
+    a.len() + b.len() - 2 * lcs
+}
+
+/// creates a full list of operations it takes to transform a to b
+///
+/// the edits use the same positions as [`super::operations`] and deletes are
+/// favored over inserts when both lead to the same cost
+pub fn operations<T>(a: &[T], b: &[T]) -> TotalEdits
+where
+    T: PartialEq
+{
+    let lcs = table(a, b);
+    let mut x = 0;
+    let mut y = 0;
+    let mut rtn = TotalEdits::default();
+
+    while x < a.len() || y < b.len() {
+        if x < a.len() && y < b.len() && a[x] == b[y] {
+            x += 1;
+            y += 1;
+        } else if x < a.len() && (y == b.len() || lcs[x + 1][y] >= lcs[x][y + 1]) {
+            rtn.deletes += 1;
+            rtn.ops.push(Edit::Delete { pos: x });
+
+            x += 1;
+        } else {
+            rtn.inserts += 1;
+            rtn.ops.push(Edit::Insert { pos: x, value: y });
+
+            y += 1;
+        }
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::edit::Alignment;
+    use crate::shortest_edit;
+
+    /// pairs of inputs to compare the Myers forms against the table with
+    ///
+    /// starts with the edge cases followed by random inputs over a small
+    /// alphabet so that there are plenty of matches and snakes
+    pub fn inputs() -> Vec<(Vec<u8>, Vec<u8>)> {
+        let mut rtn = vec![
+            (b"".to_vec(), b"".to_vec()),
+            (b"".to_vec(), b"abc".to_vec()),
+            (b"abc".to_vec(), b"".to_vec()),
+            (b"abcabba".to_vec(), b"abcabba".to_vec()),
+            (b"aaaa".to_vec(), b"bbbbbb".to_vec()),
+            (b"abcabba".to_vec(), b"cbabac".to_vec()),
+            (b"bc".to_vec(), b"cbacaaac".to_vec()),
+        ];
+
+        // xorshift so the inputs are the same on every run
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut next = move |max: u64| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+
+            (state % max) as usize
+        };
+
+        for _ in 0..500 {
+            let a = (0..next(12)).map(|_| b"abc"[next(3)]).collect();
+            let b = (0..next(12)).map(|_| b"abc"[next(3)]).collect();
+
+            rtn.push((a, b));
+        }
+
+        rtn
+    }
+
+    /// checks that the edits convert a to b
+    pub fn check_edits(a: &[u8], b: &[u8], edits: &[Edit]) {
+        let mut from = Vec::new();
+        let mut to = Vec::new();
+
+        for aligned in Alignment::new(a, b, edits.iter().copied()) {
+            if let (Some((_, a_value)), Some((_, b_value))) = aligned {
+                assert_eq!(a_value, b_value, "{a:?} -> {b:?} {edits:?}");
+            }
+
+            from.extend(aligned.0.map(|(_, value)| *value));
+            to.extend(aligned.1.map(|(_, value)| *value));
+        }
+
+        assert_eq!(from, a, "{edits:?}");
+        assert_eq!(to, b, "{edits:?}");
+    }
+
+    #[test]
+    fn operations_are_shortest() {
+        for (a, b) in inputs() {
+            let result = operations(&a, &b);
+
+            assert_eq!(result.ops.len(), distance(&a, &b), "{a:?} -> {b:?}");
+            assert_eq!(result.inserts + result.deletes, result.ops.len());
+
+            check_edits(&a, &b, &result.ops);
+        }
+    }
+
+    #[test]
+    fn base_matches_distance() {
+        for (a, b) in inputs() {
+            assert_eq!(shortest_edit::base(&a, &b) as usize, distance(&a, &b), "{a:?} -> {b:?}");
+        }
+    }
+
+    #[test]
+    fn modified_matches_distance() {
+        for (a, b) in inputs() {
+            assert_eq!(shortest_edit::modified(&a, &b), distance(&a, &b), "{a:?} -> {b:?}");
+        }
+    }
+
+    #[test]
+    fn myers_operations_match_distance() {
+        for (a, b) in inputs() {
+            let result = shortest_edit::operations(&a, &b);
+            let edits: Vec<Edit> = result.edits.iter().map(|traced| traced.edit).collect();
+
+            assert_eq!(edits.len(), distance(&a, &b), "{a:?} -> {b:?}");
+            assert_eq!(result.inserts + result.deletes, edits.len());
+
+            check_edits(&a, &b, &edits);
+        }
+    }
+}
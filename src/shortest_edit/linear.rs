@@ -1,57 +1,59 @@
 use std::cmp::{max, min};
+use std::ops::Range;
 
-use crate::array::{gi, si, gs};
-use crate::edit::{Edit, TotalEdits};
+use crate::array::{gi, si};
+use crate::edit::{Alignment, Edit, TotalEdits};
 
-/// a modified linear form of the shortest edit algorithm
-///
-/// there are modifications made to the modified version shown in below url. it
-/// is not a one-to-one example
+/// the middle snake of an edit graph
 ///
-/// source: https://blog.robertelder.org/diff-algorithm/
-pub fn modified(left: &[char], right: &[char], left_index: usize, right_index: usize) -> TotalEdits {
-    // two primary base cases for when either left or right is empty
-    if left.is_empty() {
-        // mark insert points since the left list is empty
-        let mut rtn = Vec::with_capacity(right.len());
-
-        for v in 0..right.len() {
-            rtn.push(Edit::Insert {
-                pos: left_index,
-                value: right[v],
-            });
-        }
-
-        return TotalEdits {
-            inserts: right.len(),
-            deletes: 0,
-            ops: rtn
-        };
-    } else if right.is_empty() {
-        // mark delete points since the right list is empty
-        let mut rtn = Vec::with_capacity(left.len());
+/// `(x,y)` is the start of the snake and `(u,v)` is the end. `d` is the edit
+/// distance of the slices that the snake was found in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Snake {
+    pub x: usize,
+    pub y: usize,
+    pub u: usize,
+    pub v: usize,
+    pub d: usize,
+}
 
-        for v in 0..left.len() {
-            rtn.push(Edit::Delete {
-                pos: left_index + v,
-            });
-        }
+/// finds the middle snake of the edit graph for a and b
+///
+/// the forward pass starts at `(0,0)` and the reverse pass starts at the end
+/// with each pass taking turns for every `h`. once the two overlap the snake
+/// of the pass that caused the overlap is the middle snake. if either a or b
+/// is empty then an empty snake at the middle of the other is returned.
+pub fn middle_snake<T>(left: &[T], right: &[T]) -> Snake
+where
+    T: PartialEq
+{
+    if left.is_empty() || right.is_empty() {
+        let x = left.len() / 2;
+        let y = right.len() / 2;
 
-        return TotalEdits {
-            inserts: 0,
-            deletes: left.len(),
-            ops: rtn
-        };
+        return Snake { x, y, u: x, v: y, d: left.len() + right.len() };
     }
 
-    let left_len = left.len() as i32;
-    let right_len = right.len() as i32;
+    search(left, right, None)
+}
+
+/// the search done by [`middle_snake`] for a and b that are not empty
+///
+/// if `passes` is given each pass is added to it along with the ks calculated
+/// and the frontier after it finished
+#[allow(non_snake_case)]
+fn search<T>(left: &[T], right: &[T], mut passes: Option<&mut Vec<Pass>>) -> Snake
+where
+    T: PartialEq
+{
+    let left_len = left.len() as isize;
+    let right_len = right.len() as isize;
     let total_len = left_len + right_len;
     let Z = 2 * min(left_len, right_len) + 2;
 
     let w = left_len - right_len;
-    let mut g = vec![0i32; Z as usize];
-    let mut p = vec![0i32; Z as usize];
+    let mut g = vec![0isize; Z as usize];
+    let mut p = vec![0isize; Z as usize];
 
     let h_end = if total_len % 2 != 0 {
         total_len / 2 + 2
@@ -67,6 +69,29 @@ pub fn modified(left: &[char], right: &[char], left_index: usize, right_index: u
                 (&mut p, &mut g, 0, -1)
             };
 
+            // converts a point of the pass to the coordinates of the graph if
+            // it is inside of the graph
+            let to_graph = |a: isize, b: isize| {
+                let (x, y) = if o == 1 {
+                    (a, b)
+                } else {
+                    (left_len - a, right_len - b)
+                };
+
+                if (0..=left_len).contains(&x) && (0..=right_len).contains(&y) {
+                    Some((x as usize, y as usize))
+                } else {
+                    None
+                }
+            };
+
+            let mut pass = passes.as_ref().map(|_| Pass {
+                h,
+                forward: o == 1,
+                ks: Vec::new(),
+                frontier: Vec::new(),
+            });
+
             let start = -(h - 2 * max(0, h - right_len));
             let end = h - 2 * max(0, h - left_len) + 1;
 
@@ -97,51 +122,335 @@ pub fn modified(left: &[char], right: &[char], left_index: usize, right_index: u
 
                 si(c, k % Z, a);
 
+                if let (Some(pass), Some(start), Some(end)) = (pass.as_mut(), to_graph(s, t), to_graph(a, b)) {
+                    pass.ks.push(LinearKStep {
+                        k,
+                        start,
+                        end,
+                        snake: (a - s) as usize,
+                    });
+                }
+
                 let z = -(k - w);
+                let found = total_len % 2 == o
+                    && z >= -(h - o)
+                    && z <= h - o
+                    && *gi(c, k % Z) + *gi(d, z % Z) >= left_len;
+
+                if found {
+                    if let (Some(passes), Some(mut pass)) = (passes, pass) {
+                        pass.frontier = c.clone();
+                        passes.push(pass);
+                    }
 
-                if total_len % 2 == o && z >= -(h - o) && z <= h - o && *gi(c, k % Z) + *gi(d, z % Z) >= left_len {
                     let (D, x, y, u, v) = if o == 1 {
                         (2 * h - 1, s, t, a, b)
                     } else {
                         (2 * h, left_len - a, right_len - b, left_len - s, right_len - t)
                     };
 
-                    return if D > 1 || (x != u && y != v) {
-                        let rtn_1 = modified(
-                            gs(left, 0, x),
-                            gs(right, 0, y),
-                            left_index,
-                            right_index,
-                        );
-                        let rtn_2 = modified(
-                            gs(left, u, left_len),
-                            gs(right, v, right_len),
-                            left_index + (u as usize),
-                            right_index + (v as usize),
-                        );
-
-                        rtn_1.merge(rtn_2)
-                    } else if right_len > left_len {
-                        modified(
-                            &[],
-                            &right[left.len()..right.len()],
-                            left_index + left.len(),
-                            right_index + left.len(),
-                        )
-                    } else if right_len < left_len {
-                        modified(
-                            &left[right.len()..left.len()],
-                            &[],
-                            left_index + right.len(),
-                            right_index + right.len(),
-                        )
-                    } else {
-                        TotalEdits::default()
-                    }
+                    return Snake {
+                        x: x as usize,
+                        y: y as usize,
+                        u: u as usize,
+                        v: v as usize,
+                        d: D as usize,
+                    };
+                }
+            }
+
+            if let (Some(passes), Some(mut pass)) = (passes.as_mut(), pass) {
+                pass.frontier = c.clone();
+                passes.push(pass);
+            }
+        }
+    }
+
+    unreachable!("a middle snake is always found when a and b are not empty")
+}
+
+/// splits a and b around the middle snake into the slices left to solve
+///
+/// if the snake has more than one edit the slices before and after the snake
+/// are returned. otherwise the only edit is at the end of the longer one.
+fn split(left_len: usize, right_len: usize, snake: Snake) -> Vec<(Range<usize>, Range<usize>)> {
+    let Snake { x, y, u, v, d } = snake;
+
+    if d > 1 || (x != u && y != v) {
+        vec![(0..x, 0..y), (u..left_len, v..right_len)]
+    } else if right_len > left_len {
+        vec![(left_len..left_len, left_len..right_len)]
+    } else if right_len < left_len {
+        vec![(right_len..left_len, right_len..right_len)]
+    } else {
+        Vec::new()
+    }
+}
+
+/// a modified linear form of the shortest edit algorithm
+///
+/// there are modifications made to the modified version shown in below url. it
+/// is not a one-to-one example
+///
+/// source: https://blog.robertelder.org/diff-algorithm/
+pub fn modified<T>(left: &[T], right: &[T], left_index: usize, right_index: usize) -> TotalEdits
+where
+    T: PartialEq
+{
+    // two primary base cases for when either left or right is empty
+    if left.is_empty() {
+        // mark insert points since the left list is empty
+        let mut rtn = Vec::with_capacity(right.len());
+
+        for v in 0..right.len() {
+            rtn.push(Edit::Insert {
+                pos: left_index,
+                value: right_index + v,
+            });
+        }
+
+        return TotalEdits {
+            inserts: right.len(),
+            deletes: 0,
+            ops: rtn
+        };
+    } else if right.is_empty() {
+        // mark delete points since the right list is empty
+        let mut rtn = Vec::with_capacity(left.len());
+
+        for v in 0..left.len() {
+            rtn.push(Edit::Delete {
+                pos: left_index + v,
+            });
+        }
+
+        return TotalEdits {
+            inserts: 0,
+            deletes: left.len(),
+            ops: rtn
+        };
+    }
+
+    let snake = middle_snake(left, right);
+
+    split(left.len(), right.len(), snake)
+        .into_iter()
+        .fold(TotalEdits::default(), |rtn, (left_range, right_range)| rtn.merge(modified(
+            &left[left_range.clone()],
+            &right[right_range.clone()],
+            left_index + left_range.start,
+            right_index + right_range.start,
+        )))
+}
+
+/// the edits needed to convert a to b using [`modified`]
+///
+/// the two halves of [`modified`] can leave the inserts of a change in front
+/// of its deletes so the deletes of each change are moved before its inserts
+/// the same as [`crate::shortest_edit::operations`] gives them
+pub fn edits<T>(a: &[T], b: &[T]) -> Vec<Edit>
+where
+    T: PartialEq
+{
+    let mut rtn = Vec::new();
+    let mut inserts = Vec::new();
+    // the position in a after the previous edit
+    let mut x = 0;
+
+    for edit in modified(a, b, 0, 0).ops {
+        let pos = match edit {
+            Edit::Delete { pos } | Edit::Insert { pos, .. } => pos,
+        };
+
+        if pos != x {
+            rtn.extend(inserts.drain(..).map(|value| Edit::Insert { pos: x, value }));
+        }
+
+        match edit {
+            Edit::Delete { pos } => {
+                rtn.push(edit);
+                x = pos + 1;
+            }
+            Edit::Insert { pos, value } => {
+                inserts.push(value);
+                x = pos;
+            }
+        }
+    }
+
+    rtn.extend(inserts.into_iter().map(|value| Edit::Insert { pos: x, value }));
+    rtn
+}
+
+/// creates the full two column alignment of a and b
+///
+/// same as [`crate::shortest_edit::alignment`] but uses the edits from
+/// [`edits`] so the memory used only grows with the size of a and b and not
+/// with the number of edits
+pub fn alignment<'a, T>(a: &'a [T], b: &'a [T]) -> Alignment<'a, T>
+where
+    T: PartialEq
+{
+    Alignment::new(a, b, edits(a, b))
+}
+
+/// a single k calculated in either the forward or reverse pass
+///
+/// the points are in the coordinates of the graph so the reverse pass will
+/// have its points move up and to the left. `k` is the diagonal used by the
+/// pass which is mirrored for the reverse pass. the outer diagonals of a pass
+/// can reach past the ends of the graph so only the ks with both points inside
+/// of it are kept.
+pub struct LinearKStep {
+    pub k: isize,
+    pub start: (usize, usize),
+    pub end: (usize, usize),
+    pub snake: usize,
+}
+
+/// the results of one pass for a given `h`
+pub struct Pass {
+    pub h: isize,
+    pub forward: bool,
+    pub ks: Vec<LinearKStep>,
+    /// the `g` or `p` array after the pass finished, indexed by `k % Z`
+    pub frontier: Vec<isize>,
+}
+
+/// a single call of the recursion
+pub struct Level {
+    pub left_index: usize,
+    pub right_index: usize,
+    pub left_len: usize,
+    pub right_len: usize,
+    pub passes: Vec<Pass>,
+    /// the middle snake found, if the level was not a base case
+    pub snake: Option<Snake>,
+    pub children: Vec<Level>,
+    pub edits: TotalEdits,
+}
+
+/// similar to modified but will return a tree of each level of the recursion
+/// along with the passes calculated for each `h`
+pub fn printed<T>(left: &[T], right: &[T], left_index: usize, right_index: usize) -> Level
+where
+    T: PartialEq
+{
+    let mut rtn = Level {
+        left_index,
+        right_index,
+        left_len: left.len(),
+        right_len: right.len(),
+        passes: Vec::new(),
+        snake: None,
+        children: Vec::new(),
+        edits: TotalEdits::default(),
+    };
+
+    if left.is_empty() || right.is_empty() {
+        rtn.edits = modified(left, right, left_index, right_index);
+
+        return rtn;
+    }
+
+    let snake = search(left, right, Some(&mut rtn.passes));
+
+    rtn.snake = Some(snake);
+
+    for (left_range, right_range) in split(left.len(), right.len(), snake) {
+        let child = printed(
+            &left[left_range.clone()],
+            &right[right_range.clone()],
+            left_index + left_range.start,
+            right_index + right_range.start,
+        );
+
+        rtn.edits.inserts += child.edits.inserts;
+        rtn.edits.deletes += child.edits.deletes;
+        rtn.edits.ops.extend(child.edits.ops.iter().copied());
+        rtn.children.push(child);
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shortest_edit::dp::{self, tests::{check_edits, inputs}};
+
+    #[test]
+    fn middle_snake_matches_distance() {
+        for (a, b) in inputs() {
+            let Snake { x, y, u, v, d } = middle_snake(&a, &b);
+
+            assert_eq!(d, dp::distance(&a, &b), "{a:?} -> {b:?}");
+            assert_eq!(u - x, v - y, "{a:?} -> {b:?}");
+            assert_eq!(a[x..u], b[y..v], "{a:?} -> {b:?}");
+        }
+    }
+
+    #[test]
+    fn modified_is_shortest() {
+        for (a, b) in inputs() {
+            let result = modified(&a, &b, 0, 0);
+
+            assert_eq!(result.ops.len(), dp::distance(&a, &b), "{a:?} -> {b:?}");
+            assert_eq!(result.inserts + result.deletes, result.ops.len());
+
+            check_edits(&a, &b, &result.ops);
+        }
+    }
+
+    #[test]
+    fn edits_put_deletes_before_inserts() {
+        for (a, b) in inputs() {
+            let result = edits(&a, &b);
+
+            assert_eq!(result.len(), dp::distance(&a, &b), "{a:?} -> {b:?}");
+
+            check_edits(&a, &b, &result);
+
+            let rows: Vec<_> = Alignment::new(&a, &b, result).collect();
+
+            for pair in rows.windows(2) {
+                assert!(!matches!(pair, [(None, Some(_)), (Some(_), None)]), "{a:?} -> {b:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn printed_matches_modified() {
+        for (a, b) in inputs() {
+            let level = printed(&a, &b, 0, 0);
+
+            assert_eq!(level.edits.ops, modified(&a, &b, 0, 0).ops, "{a:?} -> {b:?}");
+        }
+    }
+
+    /// checks that every point of the level and its children is inside of it
+    fn check_points(level: &Level) {
+        for pass in &level.passes {
+            for step in &pass.ks {
+                for (x, y) in [step.start, step.end] {
+                    assert!(x <= level.left_len && y <= level.right_len, "({x},{y}) in h: {}", pass.h);
                 }
             }
         }
+
+        for child in &level.children {
+            check_points(child);
+        }
     }
 
-    TotalEdits::default()
+    #[test]
+    fn printed_points_stay_inside_the_level() {
+        let left: Vec<char> = "bc".chars().collect();
+        let right: Vec<char> = "cbacaaac".chars().collect();
+        let level = printed(&left, &right, 0, 0);
+
+        check_points(&level);
+
+        assert_eq!(level.edits.ops.len(), 6);
+    }
 }
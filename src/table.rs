@@ -0,0 +1,328 @@
+//! diff of two CSV tables by row and cell
+//!
+//! the rows are aligned with [`shortest_edit::linear::edits`]. if key
+//! columns are given only the keys of the rows are compared when aligning so a
+//! row whose key is in both tables is reported as modified with the cells that
+//! changed. without keys whole rows are compared and the rows removed and
+//! added in the same place are paired up as modified rows.
+//!
+//! columns are matched by their name in the header so tables with columns in
+//! a different order or with columns added or removed can still be compared.
+
+use std::fmt::Write;
+
+use crate::edit::{pair, Alignment, Chunk, Chunks, Edit};
+use crate::shortest_edit;
+
+/// a parsed CSV file with its header
+pub struct Table {
+    pub header: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    /// parses the text using the first record as the header
+    pub fn parse(given: &str, delimiter: u8) -> Result<Self, csv::Error> {
+        let mut reader = csv::ReaderBuilder::new()
+            .delimiter(delimiter)
+            .flexible(true)
+            .from_reader(given.as_bytes());
+
+        let header = reader.headers()?
+            .iter()
+            .map(String::from)
+            .collect();
+        let mut rows = Vec::new();
+
+        for record in reader.records() {
+            rows.push(record?.iter().map(String::from).collect());
+        }
+
+        Ok(Table { header, rows })
+    }
+}
+
+/// a single cell of a modified row
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Cell {
+    Same(String),
+    Changed(String, String),
+}
+
+/// a row that is different between the tables
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Row {
+    Added(Vec<String>),
+    Removed(Vec<String>),
+    Modified(Vec<Cell>),
+}
+
+/// the columns of both tables along with the rows that changed
+pub struct TableDiff {
+    pub columns: Vec<String>,
+    pub rows: Vec<Row>,
+}
+
+/// reorders the rows of the table to line up with the given columns
+///
+/// columns missing from the table are left empty
+fn normalize(table: &Table, columns: &[String]) -> Vec<Vec<String>> {
+    let lookup: Vec<Option<usize>> = columns.iter()
+        .map(|column| table.header.iter().position(|name| name == column))
+        .collect();
+
+    table.rows.iter()
+        .map(|row| lookup.iter()
+            .map(|found| found.and_then(|index| row.get(index)).cloned().unwrap_or_default())
+            .collect())
+        .collect()
+}
+
+/// compares the cells of the two rows
+fn modified(a: &[String], b: &[String]) -> Row {
+    let cells = a.iter()
+        .zip(b)
+        .map(|(a_cell, b_cell)| if a_cell == b_cell {
+            Cell::Same(a_cell.clone())
+        } else {
+            Cell::Changed(a_cell.clone(), b_cell.clone())
+        })
+        .collect();
+
+    Row::Modified(cells)
+}
+
+/// the columns of both tables with the columns of a first
+fn columns(a: &Table, b: &Table) -> Vec<String> {
+    let mut rtn = a.header.clone();
+
+    for name in &b.header {
+        if !rtn.contains(name) {
+            rtn.push(name.clone());
+        }
+    }
+
+    rtn
+}
+
+/// checks if the tables have the same rows once their columns are lined up
+pub fn same(a: &Table, b: &Table) -> bool {
+    if a.rows.len() != b.rows.len() {
+        return false;
+    }
+
+    let columns = columns(a, b);
+
+    normalize(a, &columns) == normalize(b, &columns)
+}
+
+/// checks that each of the key columns is in the header of both tables
+pub fn check_keys(a: &Table, b: &Table, keys: &[String]) -> Result<(), String> {
+    for key in keys {
+        if !a.header.contains(key) || !b.header.contains(key) {
+            return Err(format!("key column \"{key}\" is not in both headers"));
+        }
+    }
+
+    Ok(())
+}
+
+/// finds the rows that changed between a and b
+///
+/// `keys` are the names of the columns used to match up rows and must be in
+/// the header of both tables
+pub fn diff(a: &Table, b: &Table, keys: &[String]) -> Result<TableDiff, String> {
+    check_keys(a, b, keys)?;
+
+    let columns = columns(a, b);
+    let key_indices: Vec<usize> = keys.iter()
+        .map(|key| columns.iter().position(|name| name == key).unwrap())
+        .collect();
+
+    let a_rows = normalize(a, &columns);
+    let b_rows = normalize(b, &columns);
+
+    let edits: Vec<Edit> = if key_indices.is_empty() {
+        shortest_edit::linear::edits(&a_rows, &b_rows)
+    } else {
+        let key_of = |row: &Vec<String>| -> Vec<String> {
+            key_indices.iter().map(|index| row[*index].clone()).collect()
+        };
+        let a_keys: Vec<Vec<String>> = a_rows.iter().map(key_of).collect();
+        let b_keys: Vec<Vec<String>> = b_rows.iter().map(key_of).collect();
+
+        shortest_edit::linear::edits(&a_keys, &b_keys)
+    };
+
+    let mut rows = Vec::new();
+
+    for chunk in Chunks::new(Alignment::new(&a_rows, &b_rows, edits)) {
+        match chunk {
+            Chunk::Same((_, a_row), (_, b_row)) => if a_row != b_row {
+                rows.push(modified(a_row, b_row));
+            },
+            // without keys the removed and added rows are paired with each
+            // other in order with any left over being added on their own
+            Chunk::Change(removed, added) => if key_indices.is_empty() {
+                for paired in pair(removed, added) {
+                    match paired {
+                        (Some((_, a_row)), Some((_, b_row))) => rows.push(modified(a_row, b_row)),
+                        (Some((_, a_row)), None) => rows.push(Row::Removed(a_row.clone())),
+                        (None, Some((_, b_row))) => rows.push(Row::Added(b_row.clone())),
+                        (None, None) => {}
+                    }
+                }
+            } else {
+                rows.extend(removed.into_iter().map(|(_, row)| Row::Removed(row.clone())));
+                rows.extend(added.into_iter().map(|(_, row)| Row::Added(row.clone())));
+            },
+        }
+    }
+
+    Ok(TableDiff { columns, rows })
+}
+
+/// renders the changed rows as a table
+///
+/// added rows are marked with `+`, removed rows with `-` and modified rows
+/// with `~` where each changed cell shows the old and new value. nothing is
+/// rendered if no rows changed
+pub fn render(diff: &TableDiff) -> String {
+    if diff.rows.is_empty() {
+        return String::new();
+    }
+
+    let mut lines: Vec<(&str, Vec<String>)> = Vec::with_capacity(diff.rows.len() + 1);
+
+    lines.push(("", diff.columns.clone()));
+
+    for row in &diff.rows {
+        match row {
+            Row::Added(cells) => lines.push(("+", cells.clone())),
+            Row::Removed(cells) => lines.push(("-", cells.clone())),
+            Row::Modified(cells) => lines.push(("~", cells.iter()
+                .map(|cell| match cell {
+                    Cell::Same(value) => value.clone(),
+                    Cell::Changed(from, to) => format!("{from} -> {to}"),
+                })
+                .collect())),
+        }
+    }
+
+    let mut widths = vec![0; diff.columns.len()];
+
+    for (_, cells) in &lines {
+        for (width, cell) in widths.iter_mut().zip(cells) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut rtn = String::new();
+
+    for (marker, cells) in &lines {
+        let mut line = format!("{marker:1}");
+
+        for (width, cell) in widths.iter().zip(cells) {
+            write!(line, " | {cell:width$}").unwrap();
+        }
+
+        writeln!(rtn, "{}", line.trim_end()).unwrap();
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// parses the comma separated text
+    fn table(given: &str) -> Table {
+        Table::parse(given, b',').unwrap()
+    }
+
+    /// the cells of the row as owned strings
+    fn cells(given: &[&str]) -> Vec<String> {
+        given.iter().map(|cell| cell.to_string()).collect()
+    }
+
+    #[test]
+    fn unkeyed_rows_are_paired_in_order() {
+        let a = table("id,name\n1,a\n2,b\n3,c\n");
+        let b = table("id,name\n1,a\n2,x\n4,d\n5,e\n");
+        let result = diff(&a, &b, &[]).unwrap();
+
+        assert_eq!(result.rows, [
+            Row::Modified(vec![Cell::Same("2".into()), Cell::Changed("b".into(), "x".into())]),
+            Row::Modified(vec![Cell::Changed("3".into(), "4".into()), Cell::Changed("c".into(), "d".into())]),
+            Row::Added(cells(&["5", "e"])),
+        ]);
+    }
+
+    #[test]
+    fn keyed_rows_are_matched_by_key() {
+        let a = table("id,name\n1,a\n2,b\n3,c\n");
+        let b = table("id,name\n1,a\n2,x\n4,d\n");
+        let result = diff(&a, &b, &cells(&["id"])).unwrap();
+
+        assert_eq!(result.rows, [
+            Row::Modified(vec![Cell::Same("2".into()), Cell::Changed("b".into(), "x".into())]),
+            Row::Removed(cells(&["3", "c"])),
+            Row::Added(cells(&["4", "d"])),
+        ]);
+    }
+
+    #[test]
+    fn reordered_columns_are_lined_up() {
+        let a = table("id,name\n1,a\n");
+        let b = table("name,id\na,1\n");
+
+        assert!(same(&a, &b));
+        assert!(diff(&a, &b, &[]).unwrap().rows.is_empty());
+    }
+
+    #[test]
+    fn added_columns_are_empty_in_the_other_table() {
+        let a = table("id,name\n1,a\n");
+        let b = table("id,name,size\n1,a,3\n");
+        let result = diff(&a, &b, &cells(&["id"])).unwrap();
+
+        assert_eq!(result.columns, cells(&["id", "name", "size"]));
+        assert_eq!(result.rows, [Row::Modified(vec![
+            Cell::Same("1".into()),
+            Cell::Same("a".into()),
+            Cell::Changed("".into(), "3".into()),
+        ])]);
+        assert!(!same(&a, &b));
+    }
+
+    #[test]
+    fn same_agrees_with_diff() {
+        let inputs = [
+            ("id,name\n1,a\n", "id,name\n1,a\n"),
+            ("id,name\n1,a\n", "name,id\na,1\n"),
+            ("id,name\n1,a\n", "id,name\n1,b\n"),
+            ("id,name\n1,a\n", "id,name\n1,a\n2,b\n"),
+            ("id,name\n1,a\n2,b\n", "id,name\n2,b\n1,a\n"),
+            ("id\n1\n", "id,name\n1,\n"),
+            ("id\n1\n", "id,name\n1,a\n"),
+        ];
+
+        for (a, b) in inputs {
+            let (a, b) = (table(a), table(b));
+
+            for keys in [Vec::new(), cells(&["id"])] {
+                assert_eq!(same(&a, &b), diff(&a, &b, &keys).unwrap().rows.is_empty(), "{:?} {:?}", a.rows, b.rows);
+            }
+        }
+    }
+
+    #[test]
+    fn keys_must_be_in_both_headers() {
+        let a = table("id,name\n1,a\n");
+        let b = table("name\na\n");
+
+        assert_eq!(check_keys(&a, &b, &cells(&["id"])), Err(String::from("key column \"id\" is not in both headers")));
+        assert!(diff(&a, &b, &cells(&["id"])).is_err());
+    }
+}
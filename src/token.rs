@@ -0,0 +1,217 @@
+//! splits text into tokens to be diffed
+//!
+//! the lexer does not know about any specific language. it splits the text
+//! into identifiers, numbers, quoted strings, single punctuation characters
+//! and runs of whitespace. newlines are always their own token so that changes
+//! do not run across lines. joining the tokens gives back the original text.
+//!
+//! words are split either by whitespace or by a pattern. with a pattern the
+//! text between the matches is kept as gaps that are split on newlines the
+//! same as the whitespace between words. like git's `--word-diff-regex` any
+//! change to a gap is ignored.
+
+use std::fmt::Display;
+
+use regex::Regex;
+
+/// a piece of the text split by [`matches`]
+///
+/// a gap is equal to any other gap so only the matches and newlines are
+/// compared
+#[derive(Debug, Clone, Copy)]
+pub struct Piece<'a> {
+    pub text: &'a str,
+    pub gap: bool,
+}
+
+impl PartialEq for Piece<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self.gap, other.gap) {
+            (true, true) => true,
+            (false, false) => self.text == other.text,
+            _ => false,
+        }
+    }
+}
+
+impl Display for Piece<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.text)
+    }
+}
+
+/// checks if the character can be part of an identifier
+fn is_ident(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// finds the end of the quoted string starting at `start`
+///
+/// the string ends at the matching quote or at the end of the line if it is
+/// not closed. a backslash escapes the character after it.
+fn string_end(given: &str, start: usize, quote: char) -> usize {
+    let mut chars = given[start..].char_indices().skip(1);
+
+    while let Some((offset, ch)) = chars.next() {
+        match ch {
+            '\\' => if let Some((offset, '\n')) = chars.next() {
+                return start + offset;
+            },
+            '\n' => return start + offset,
+            _ if ch == quote => return start + offset + ch.len_utf8(),
+            _ => {}
+        }
+    }
+
+    given.len()
+}
+
+/// finds the end of the run of characters starting at `start` that match
+fn run_end<F>(given: &str, start: usize, matches: F) -> usize
+where
+    F: Fn(char) -> bool
+{
+    given[start..].char_indices()
+        .find(|(_, ch)| !matches(*ch))
+        .map_or(given.len(), |(offset, _)| start + offset)
+}
+
+/// splits the text into tokens
+pub fn tokenize(given: &str) -> Vec<&str> {
+    let mut rtn = Vec::new();
+    let mut start = 0;
+
+    while let Some(ch) = given[start..].chars().next() {
+        let end = if ch == '\n' {
+            start + 1
+        } else if ch.is_whitespace() {
+            run_end(given, start, |ch| ch.is_whitespace() && ch != '\n')
+        } else if ch.is_numeric() {
+            run_end(given, start, |ch| is_ident(ch) || ch == '.')
+        } else if is_ident(ch) {
+            run_end(given, start, is_ident)
+        } else if ch == '"' || ch == '\'' || ch == '`' {
+            string_end(given, start, ch)
+        } else {
+            start + ch.len_utf8()
+        };
+
+        rtn.push(&given[start..end]);
+        start = end;
+    }
+
+    rtn
+}
+
+/// splits the text into words and the whitespace between them
+pub fn words(given: &str) -> Vec<&str> {
+    let mut rtn = Vec::new();
+    let mut start = 0;
+
+    while let Some(ch) = given[start..].chars().next() {
+        let end = if ch == '\n' {
+            start + 1
+        } else if ch.is_whitespace() {
+            run_end(given, start, |ch| ch.is_whitespace() && ch != '\n')
+        } else {
+            run_end(given, start, |ch| !ch.is_whitespace())
+        };
+
+        rtn.push(&given[start..end]);
+        start = end;
+    }
+
+    rtn
+}
+
+/// adds the text between two matches as gaps with each newline on its own
+fn push_gaps<'a>(rtn: &mut Vec<Piece<'a>>, given: &'a str) {
+    for (index, line) in given.split('\n').enumerate() {
+        if index > 0 {
+            rtn.push(Piece { text: "\n", gap: false });
+        }
+
+        if !line.is_empty() {
+            rtn.push(Piece { text: line, gap: true });
+        }
+    }
+}
+
+/// splits the text into the matches of the pattern and the gaps between them
+pub fn matches<'a>(given: &'a str, pattern: &Regex) -> Vec<Piece<'a>> {
+    let mut rtn = Vec::new();
+    let mut start = 0;
+
+    for found in pattern.find_iter(given) {
+        if found.is_empty() {
+            continue;
+        }
+
+        push_gaps(&mut rtn, &given[start..found.start()]);
+        rtn.push(Piece { text: found.as_str(), gap: false });
+        start = found.end();
+    }
+
+    push_gaps(&mut rtn, &given[start..]);
+
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tokens_join_back_into_the_text() {
+        let text = "fn main() {\n\tlet x_1 = 1.5e3 + \"a \\\" b\";\r\n  'c' `d`\n}\n";
+
+        assert_eq!(tokenize(text).concat(), text);
+        assert_eq!(tokenize(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn tokens_by_class() {
+        assert_eq!(tokenize("foo_bar1 baz"), ["foo_bar1", " ", "baz"]);
+        assert_eq!(tokenize("12.5f+3"), ["12.5f", "+", "3"]);
+        assert_eq!(tokenize("a \t b"), ["a", " \t ", "b"]);
+        assert_eq!(tokenize("a\n\nb"), ["a", "\n", "\n", "b"]);
+        assert_eq!(tokenize(" \n "), [" ", "\n", " "]);
+        assert_eq!(tokenize("(a,b);"), ["(", "a", ",", "b", ")", ";"]);
+        assert_eq!(tokenize("é"), ["é"]);
+        assert_eq!(tokenize("→x"), ["→", "x"]);
+    }
+
+    #[test]
+    fn strings_are_single_tokens() {
+        assert_eq!(tokenize("x = \"a b\";"), ["x", " ", "=", " ", "\"a b\"", ";"]);
+        assert_eq!(tokenize("'it\\'s' `t`"), ["'it\\'s'", " ", "`t`"]);
+    }
+
+    #[test]
+    fn unterminated_strings_end_at_the_line() {
+        assert_eq!(tokenize("\"abc\nd"), ["\"abc", "\n", "d"]);
+        assert_eq!(tokenize("\"abc"), ["\"abc"]);
+        assert_eq!(tokenize("\"a\\\nb\""), ["\"a\\", "\n", "b", "\""]);
+    }
+
+    #[test]
+    fn matches_split_gaps_on_newlines() {
+        let pattern = Regex::new("[a-z]+").unwrap();
+        let pieces = matches("x = 1;\n    y", &pattern);
+        let texts: Vec<&str> = pieces.iter().map(|piece| piece.text).collect();
+        let gaps: Vec<bool> = pieces.iter().map(|piece| piece.gap).collect();
+
+        assert_eq!(texts, ["x", " = 1;", "\n", "    ", "y"]);
+        assert_eq!(gaps, [false, true, false, true, false]);
+        assert_eq!(texts.concat(), "x = 1;\n    y");
+    }
+
+    #[test]
+    fn matches_ignore_changes_to_gaps() {
+        let pattern = Regex::new("[a-z]+").unwrap();
+
+        assert_eq!(matches("x = 1;\n", &pattern), matches("x := 2\n", &pattern));
+        assert_ne!(matches("x = 1;\n", &pattern), matches("x = 1; y\n", &pattern));
+        assert_ne!(matches("x y", &pattern), matches("x\ny", &pattern));
+    }
+}
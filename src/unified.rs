@@ -0,0 +1,215 @@
+//! renders an alignment as the hunks of a unified diff
+//!
+//! changes that are close to each other are grouped into a single hunk with
+//! the given number of unchanged lines around them. each hunk starts with a
+//! `@@ -start,count +start,count @@` header followed by the lines prefixed
+//! with ` ` if unchanged, `-` if deleted and `+` if inserted.
+
+use std::fmt::{Display, Write};
+
+use crate::edit::Aligned;
+
+/// the start and count of a hunk for one side in the form used by the header
+///
+/// the start is the line before the hunk if it has no lines from this side
+fn range(before: usize, count: usize) -> String {
+    if count == 0 {
+        format!("{before},0")
+    } else if count == 1 {
+        format!("{}", before + 1)
+    } else {
+        format!("{},{count}", before + 1)
+    }
+}
+
+/// renders the hunks of the alignment with `context` unchanged lines around
+/// each change
+///
+/// `suppressed` marks the rows of changes that can be ignored. they are still
+/// grouped into hunks like any other change but a hunk is only shown if it has
+/// a change that is not suppressed.
+///
+/// `a_newline` and `b_newline` are if a and b end with a newline. the last
+/// line of a side that does not is followed by `\ No newline at end of file`
+pub fn render<T>(rows: &[Aligned<T>], suppressed: &[bool], context: usize, a_newline: bool, b_newline: bool) -> String
+where
+    T: Display
+{
+    let a_len = rows.iter().filter(|aligned| aligned.0.is_some()).count();
+    let b_len = rows.iter().filter(|aligned| aligned.1.is_some()).count();
+
+    // checks if the row has the last line of a side that has no newline
+    let no_newline = |aligned: &Aligned<T>| {
+        aligned.0.is_some_and(|(x, _)| !a_newline && x + 1 == a_len)
+            || aligned.1.is_some_and(|(y, _)| !b_newline && y + 1 == b_len)
+    };
+
+    // the first and last row of each change
+    let mut changes: Vec<(usize, usize)> = Vec::new();
+
+    for (index, aligned) in rows.iter().enumerate() {
+        if aligned.0.is_some() && aligned.1.is_some() {
+            continue;
+        }
+
+        match changes.last_mut() {
+            Some((_, last)) if *last + 1 == index => *last = index,
+            _ => changes.push((index, index)),
+        }
+    }
+
+    let mut rtn = String::new();
+    let mut index = 0;
+
+    while index < changes.len() {
+        let (first, mut last) = changes[index];
+        let mut shown = !suppressed[first];
+
+        index += 1;
+
+        // a suppressed change is only added to the hunk if it is within the
+        // context of the hunk the same as diff
+        while let Some(&(next_first, next_last)) = changes.get(index) {
+            let limit = if suppressed[next_first] { context } else { context * 2 + 1 };
+
+            if next_first - last > limit {
+                break;
+            }
+
+            last = next_last;
+            shown |= !suppressed[next_first];
+            index += 1;
+        }
+
+        if !shown {
+            continue;
+        }
+
+        let start = first.saturating_sub(context);
+        let end = (last + context + 1).min(rows.len());
+        let hunk = &rows[start..end];
+
+        let a_before = rows[..start].iter().filter(|aligned| aligned.0.is_some()).count();
+        let b_before = rows[..start].iter().filter(|aligned| aligned.1.is_some()).count();
+        let a_count = hunk.iter().filter(|aligned| aligned.0.is_some()).count();
+        let b_count = hunk.iter().filter(|aligned| aligned.1.is_some()).count();
+
+        writeln!(rtn, "@@ -{} +{} @@", range(a_before, a_count), range(b_before, b_count)).unwrap();
+
+        for aligned in hunk {
+            match aligned {
+                (Some((_, value)), Some(_)) => writeln!(rtn, " {value}").unwrap(),
+                (Some((_, value)), None) => writeln!(rtn, "-{value}").unwrap(),
+                (None, Some((_, value))) => writeln!(rtn, "+{value}").unwrap(),
+                (None, None) => {}
+            }
+
+            if no_newline(aligned) {
+                rtn.push_str("\\ No newline at end of file\n");
+            }
+        }
+    }
+
+    rtn
+}
+
+#[cfg(test)]
+mod tests {
+    use regex::Regex;
+
+    use super::*;
+    use crate::normalize::{self, Options};
+    use crate::shortest_edit;
+
+    /// renders the unified diff of the two texts with the given options
+    fn unified_with(options: &Options, a: &str, b: &str) -> String {
+        let a_lines = options.lines(a);
+        let b_lines = options.lines(b);
+        let rows: Vec<Aligned<_>> = shortest_edit::linear::alignment(&a_lines, &b_lines).collect();
+        let suppressed = normalize::suppressed(&rows);
+
+        render(&rows, &suppressed, 3, a.ends_with('\n'), b.ends_with('\n'))
+    }
+
+    /// renders the unified diff of the two texts with the default options
+    fn unified(a: &str, b: &str) -> String {
+        unified_with(&Options::default(), a, b)
+    }
+
+    /// the hunk headers of the unified diff
+    fn headers(rendered: &str) -> Vec<&str> {
+        rendered.lines().filter(|line| line.starts_with("@@")).collect()
+    }
+
+    /// numbers the lines from `start` to `end` with the given lines replaced
+    fn numbered(start: usize, end: usize, replaced: &[(usize, &str)]) -> String {
+        (start..=end)
+            .map(|number| match replaced.iter().find(|(at, _)| *at == number) {
+                Some((_, line)) => format!("{line}\n"),
+                None => format!("l{number}\n"),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn ignored_blank_lines_keep_line_numbers() {
+        let options = Options { ignore_blank_lines: true, ..Options::default() };
+        let a = format!("l1\n\n{}", numbered(2, 12, &[]));
+        let b = numbered(1, 12, &[(10, "X10")]);
+
+        // diff -B -u
+        assert_eq!(headers(&unified_with(&options, &a, &b)), ["@@ -8,6 +7,6 @@"]);
+        assert_eq!(unified_with(&options, "a\n\nb\n", "a\nb\n\n"), "");
+    }
+
+    #[test]
+    fn ignored_matching_lines_match_diff() {
+        let options = Options {
+            ignore_matching_lines: vec![Regex::new("^#").unwrap()],
+            ..Options::default()
+        };
+        let a = format!("# one\n{}# two\n{}", numbered(1, 10, &[]), numbered(11, 18, &[]));
+        let b = format!("l1\n# uno\n{}{}# tres\n", numbered(2, 10, &[]), numbered(11, 18, &[(13, "X13")]));
+
+        // diff -I '^#' -u
+        assert_eq!(headers(&unified_with(&options, &a, &b)), ["@@ -9,10 +9,9 @@"]);
+    }
+
+    #[test]
+    fn ignored_changes_join_hunks_within_context() {
+        let options = Options {
+            ignore_matching_lines: vec![Regex::new("^#").unwrap()],
+            ..Options::default()
+        };
+
+        // diff -I '^#' -u with the ignored change 2 and 3 lines after the
+        // change
+        let a = numbered(1, 20, &[(5, "# a")]);
+        let b = numbered(1, 20, &[(2, "X"), (5, "# b")]);
+
+        assert_eq!(headers(&unified_with(&options, &a, &b)), ["@@ -1,8 +1,8 @@"]);
+
+        let a = numbered(1, 20, &[(6, "# a")]);
+        let b = numbered(1, 20, &[(2, "X"), (6, "# b")]);
+
+        assert_eq!(headers(&unified_with(&options, &a, &b)), ["@@ -1,5 +1,5 @@"]);
+    }
+
+    #[test]
+    fn missing_newline_is_a_change() {
+        assert_eq!(unified("a\nb\n", "a\nb"), "@@ -1,2 +1,2 @@\n a\n-b\n+b\n\\ No newline at end of file\n");
+    }
+
+    #[test]
+    fn missing_newline_on_both_sides() {
+        assert_eq!(
+            unified("a\nb", "a\nc"),
+            "@@ -1,2 +1,2 @@\n a\n-b\n\\ No newline at end of file\n+c\n\\ No newline at end of file\n"
+        );
+        assert_eq!(
+            unified("a\nb", "x\na\nb"),
+            "@@ -1,2 +1,3 @@\n+x\n a\n b\n\\ No newline at end of file\n"
+        );
+        assert_eq!(unified("a\nb", "a\nb"), "");
+    }
+}